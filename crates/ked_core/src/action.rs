@@ -0,0 +1,343 @@
+use crate::*;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 一个物理输入，可以绑定到动作或轴上
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    MouseButton(MouseButton),
+    /// 屏幕上的一块区域（物理像素），有手指按在里面时视为按下
+    TouchRegion(Rect),
+}
+
+impl Binding {
+    fn is_down(&self, input: &Input) -> bool {
+        match self {
+            Self::Key(key) => input.is_key_down(*key),
+            Self::MouseButton(button) => input.is_mouse_button_down(*button),
+            Self::TouchRegion(region) => input
+                .touches()
+                .touches()
+                .any(|t| t.phase != TouchPhase::Ended && t.phase != TouchPhase::Cancelled
+                    && region.contains(t.position)),
+        }
+    }
+
+    fn is_pressed(&self, input: &Input) -> bool {
+        match self {
+            Self::Key(key) => input.is_key_pressed(*key),
+            Self::MouseButton(button) => input.is_mouse_button_pressed(*button),
+            Self::TouchRegion(region) => input
+                .touches()
+                .touches()
+                .any(|t| t.started_this_frame && region.contains(t.position)),
+        }
+    }
+
+    fn is_released(&self, input: &Input) -> bool {
+        match self {
+            Self::Key(key) => input.is_key_released(*key),
+            Self::MouseButton(button) => input.is_mouse_button_released(*button),
+            Self::TouchRegion(region) => input
+                .touches()
+                .touches()
+                // 在区域内按下后滑出去再抬起也要算松开，否则动作会一直保持按下
+                .any(|t| matches!(t.phase, TouchPhase::Ended | TouchPhase::Cancelled)
+                    && (region.contains(t.start_position) || region.contains(t.position))),
+        }
+    }
+}
+
+/// 一个轴由负方向和正方向两组绑定组成，取值为 -1.0 到 1.0
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AxisBinding {
+    #[serde(default)]
+    pub negative: Vec<Binding>,
+    #[serde(default)]
+    pub positive: Vec<Binding>,
+}
+
+/// 把命名的动作和轴映射到物理输入，可以从 TOML 文件读取并在运行时重新绑定
+///
+/// ```toml
+/// [actions]
+/// jump = [{ Key = "Space" }, { MouseButton = "Left" }]
+///
+/// [axes.move_x]
+/// negative = [{ Key = "KeyA" }]
+/// positive = [{ Key = "KeyD" }]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputMap {
+    #[serde(default)]
+    actions: HashMap<String, Vec<Binding>>,
+    #[serde(default)]
+    axes: HashMap<String, AxisBinding>,
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_toml_str(source: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(source)?)
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, ConfigError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ConfigError> {
+        std::fs::write(path, self.to_toml_string()?)?;
+
+        Ok(())
+    }
+
+    /// 给动作增加一个绑定，重复的绑定会被忽略
+    pub fn bind(&mut self, action: &str, binding: Binding) -> &mut Self {
+        let bindings = self.actions.entry(action.to_owned()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+
+        self
+    }
+
+    pub fn unbind(&mut self, action: &str, binding: Binding) {
+        if let Some(bindings) = self.actions.get_mut(action) {
+            bindings.retain(|b| *b != binding);
+        }
+    }
+
+    /// 把动作上的 `old` 绑定替换为 `new`，`old` 不存在时不做修改并返回 false
+    pub fn rebind(&mut self, action: &str, old: Binding, new: Binding) -> bool {
+        let Some(bindings) = self.actions.get_mut(action) else {
+            return false;
+        };
+
+        let Some(index) = bindings.iter().position(|b| *b == old) else {
+            return false;
+        };
+
+        // `new` 已经绑定在这个动作上时只移除 `old`，避免重复
+        if bindings.contains(&new) {
+            bindings.remove(index);
+        } else {
+            bindings[index] = new;
+        }
+
+        true
+    }
+
+    /// 移除动作上的所有绑定
+    pub fn clear_action(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.actions.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.keys().map(String::as_str)
+    }
+
+    /// 给轴增加一组绑定，重复的绑定会被忽略
+    pub fn bind_axis(&mut self, axis: &str, negative: Binding, positive: Binding) -> &mut Self {
+        let bindings = self.axes.entry(axis.to_owned()).or_default();
+
+        if !bindings.negative.contains(&negative) {
+            bindings.negative.push(negative);
+        }
+
+        if !bindings.positive.contains(&positive) {
+            bindings.positive.push(positive);
+        }
+
+        self
+    }
+
+    pub fn set_axis(&mut self, axis: &str, bindings: AxisBinding) {
+        self.axes.insert(axis.to_owned(), bindings);
+    }
+
+    pub fn axis_bindings(&self, axis: &str) -> Option<&AxisBinding> {
+        self.axes.get(axis)
+    }
+
+    pub fn is_action_down(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|b| b.is_down(input))
+    }
+
+    /// 本帧开始按下，其他绑定已经按住时不算
+    pub fn is_action_pressed(&self, input: &Input, action: &str) -> bool {
+        let bindings = self.bindings(action);
+
+        bindings.iter().any(|b| b.is_pressed(input))
+            && !bindings.iter().any(|b| b.is_down(input) && !b.is_pressed(input))
+    }
+
+    /// 本帧松开，并且没有其他绑定仍然按住
+    pub fn is_action_released(&self, input: &Input, action: &str) -> bool {
+        let bindings = self.bindings(action);
+
+        bindings.iter().any(|b| b.is_released(input))
+            && !bindings.iter().any(|b| b.is_down(input))
+    }
+
+    pub fn axis(&self, input: &Input, axis: &str) -> f32 {
+        let Some(bindings) = self.axes.get(axis) else {
+            return 0.0;
+        };
+
+        let negative = bindings.negative.iter().any(|b| b.is_down(input));
+        let positive = bindings.positive.iter().any(|b| b.is_down(input));
+
+        positive as i32 as f32 - negative as i32 as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_map() -> InputMap {
+        let mut map = InputMap::new();
+        map.bind("jump", Binding::Key(KeyCode::Space))
+            .bind("jump", Binding::MouseButton(MouseButton::Left))
+            .bind("fire", Binding::TouchRegion(Rect::new(0.0, 0.0, 100.0, 100.0)))
+            .bind_axis("move_x", Binding::Key(KeyCode::KeyA), Binding::Key(KeyCode::KeyD));
+
+        map
+    }
+
+    #[test]
+    fn action_press_hold_release() {
+        let map = input_map();
+        let mut input = Input::new();
+
+        input.set_key(KeyCode::Space, true);
+        assert!(map.is_action_down(&input, "jump"));
+        assert!(map.is_action_pressed(&input, "jump"));
+
+        input.end_frame();
+        assert!(map.is_action_down(&input, "jump"));
+        assert!(!map.is_action_pressed(&input, "jump"));
+
+        // 另一个绑定在按住时按下，不算新的按下
+        input.set_mouse_button(MouseButton::Left, true);
+        assert!(!map.is_action_pressed(&input, "jump"));
+
+        // 还有绑定按住，不算松开
+        input.end_frame();
+        input.set_key(KeyCode::Space, false);
+        assert!(!map.is_action_released(&input, "jump"));
+
+        input.end_frame();
+        input.set_mouse_button(MouseButton::Left, false);
+        assert!(map.is_action_released(&input, "jump"));
+        assert!(!map.is_action_down(&input, "jump"));
+    }
+
+    #[test]
+    fn axis_combines_both_directions() {
+        let map = input_map();
+        let mut input = Input::new();
+
+        assert_eq!(map.axis(&input, "move_x"), 0.0);
+        assert_eq!(map.axis(&input, "missing"), 0.0);
+
+        input.set_key(KeyCode::KeyD, true);
+        assert_eq!(map.axis(&input, "move_x"), 1.0);
+
+        input.set_key(KeyCode::KeyA, true);
+        assert_eq!(map.axis(&input, "move_x"), 0.0);
+
+        input.set_key(KeyCode::KeyD, false);
+        assert_eq!(map.axis(&input, "move_x"), -1.0);
+    }
+
+    #[test]
+    fn touch_in_region_is_pressed_even_if_it_moved() {
+        let map = input_map();
+        let mut input = Input::new();
+
+        input.touches_mut().touch(0, TouchPhase::Started, vec2(50.0, 50.0), 1.0, 0.0);
+        input.touches_mut().touch(0, TouchPhase::Moved, vec2(55.0, 50.0), 1.0, 0.004);
+
+        assert!(map.is_action_down(&input, "fire"));
+        assert!(map.is_action_pressed(&input, "fire"));
+
+        input.end_frame();
+        assert!(map.is_action_down(&input, "fire"));
+        assert!(!map.is_action_pressed(&input, "fire"));
+    }
+
+    #[test]
+    fn touch_released_outside_region_releases_action() {
+        let map = input_map();
+        let mut input = Input::new();
+
+        input.touches_mut().touch(0, TouchPhase::Started, vec2(50.0, 50.0), 1.0, 0.0);
+        input.end_frame();
+
+        input.touches_mut().touch(0, TouchPhase::Moved, vec2(500.0, 50.0), 1.0, 0.1);
+        input.touches_mut().touch(0, TouchPhase::Ended, vec2(500.0, 50.0), 1.0, 0.2);
+
+        assert!(!map.is_action_down(&input, "fire"));
+        assert!(map.is_action_released(&input, "fire"));
+    }
+
+    #[test]
+    fn bind_axis_ignores_duplicates() {
+        let mut map = input_map();
+        let before = map.axis_bindings("move_x").cloned();
+
+        map.bind_axis("move_x", Binding::Key(KeyCode::KeyA), Binding::Key(KeyCode::KeyD));
+        assert_eq!(map.axis_bindings("move_x").cloned(), before);
+    }
+
+    #[test]
+    fn tap_within_one_frame_is_pressed_and_released() {
+        let map = input_map();
+        let mut input = Input::new();
+
+        input.touches_mut().touch(0, TouchPhase::Started, vec2(50.0, 50.0), 1.0, 0.0);
+        input.touches_mut().touch(0, TouchPhase::Ended, vec2(50.0, 50.0), 1.0, 0.004);
+
+        assert!(map.is_action_pressed(&input, "fire"));
+        assert!(map.is_action_released(&input, "fire"));
+
+        input.touches_mut().touch(1, TouchPhase::Started, vec2(500.0, 50.0), 1.0, 0.0);
+        input.end_frame();
+        assert!(!map.is_action_pressed(&input, "fire"));
+        assert!(!map.is_action_released(&input, "fire"));
+    }
+
+    #[test]
+    fn rebind_only_replaces_existing_bindings() {
+        let mut map = input_map();
+
+        assert!(map.rebind("jump", Binding::Key(KeyCode::Space), Binding::Key(KeyCode::KeyW)));
+        assert_eq!(
+            map.bindings("jump"),
+            &[Binding::Key(KeyCode::KeyW), Binding::MouseButton(MouseButton::Left)]
+        );
+
+        // 旧的绑定已经不存在，不应该再添加一个
+        assert!(!map.rebind("jump", Binding::Key(KeyCode::Space), Binding::Key(KeyCode::KeyE)));
+        assert!(!map.rebind("missing", Binding::Key(KeyCode::Space), Binding::Key(KeyCode::KeyE)));
+        assert_eq!(map.bindings("jump").len(), 2);
+        assert!(map.bindings("missing").is_empty());
+
+        assert!(map.rebind("jump", Binding::Key(KeyCode::KeyW), Binding::MouseButton(MouseButton::Left)));
+        assert_eq!(map.bindings("jump"), &[Binding::MouseButton(MouseButton::Left)]);
+    }
+}
@@ -1,12 +1,29 @@
 use crate::*;
 
+use std::time::Instant;
+
+use winit::monitor::MonitorHandle;
+
 pub struct App {
     timer: Time,
+    input: Input,
+    limiter: FrameLimiter,
+    scheduler: Scheduler,
+    scenes: SceneManager,
+    resources: Resources,
+    events: Events,
+    #[cfg(feature = "ecs")]
+    world: World,
+    systems: Systems,
+    plugins: Vec<String>,
+    control_flow: ControlFlow,
     game: Box<dyn GameLoop>,
 
     wr: Option<WgpuRenderer>,
     window: Option<Arc<Window>>,
     window_config: Arc<Mutex<WindowConfig>>,
+    /// 窗口模式下最近的大小和位置，退出时保存
+    geometry: WindowGeometry,
 }
 
 impl App {
@@ -14,15 +31,93 @@ impl App {
         game: impl GameLoop + 'static, 
         window_config: WindowConfig
     ) -> Self {
+        let mut timer = Time::new();
+        timer.set_fixed_timestep(
+            Duration::from_secs_f64(1.0 / window_config.fixed_update_rate.max(1) as f64),
+            window_config.max_fixed_steps,
+        );
+
+        let window_config = Arc::new(Mutex::new(window_config));
+        let limiter = FrameLimiter::with_window_config(Arc::clone(&window_config));
+
         Self { 
             game: Box::new(game),
-            window_config: Arc::new(Mutex::new(window_config)),
+            window_config,
 
-            timer: Time::new(),
+            timer,
+            input: Input::new(),
+            limiter,
+            scheduler: Scheduler::new(),
+            scenes: SceneManager::new(),
+            resources: Resources::new(),
+            events: Events::new(),
+            #[cfg(feature = "ecs")]
+            world: World::new(),
+            systems: Systems::default(),
+            plugins: Vec::new(),
+            control_flow: ControlFlow::Poll,
 
             wr: None,
             window: None,
+            geometry: WindowGeometry::default(),
+        }
+    }
+
+    /// 替换时间来源，例如在测试中使用 `ManualClock` 精确控制每帧的时间
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.timer.set_clock(clock);
+    }
+
+    /// 添加插件，同名的插件只会生效一次
+    pub fn add_plugin(&mut self, plugin: impl Plugin) -> &mut Self {
+        let name = plugin.name().to_owned();
+        if self.plugins.contains(&name) {
+            warn!("Plugin {name} is already added");
+            return self;
         }
+
+        plugin.build(self);
+        self.plugins.push(name);
+
+        self
+    }
+
+    pub fn has_plugin(&self, name: &str) -> bool {
+        self.plugins.iter().any(|p| p == name)
+    }
+
+    pub fn insert_resource<T: 'static>(&mut self, resource: T) -> &mut Self {
+        self.resources.insert(resource);
+        self
+    }
+
+    pub fn resources(&self) -> &Resources {
+        &self.resources
+    }
+
+    pub fn resources_mut(&mut self) -> &mut Resources {
+        &mut self.resources
+    }
+
+    /// 注册一个在 `stage` 阶段每帧执行的系统
+    pub fn add_system(
+        &mut self,
+        stage: SystemStage,
+        system: impl FnMut(&mut EngineContext) + 'static,
+    ) -> &mut Self {
+        self.systems.add_system(stage, Box::new(system));
+        self
+    }
+
+    /// 注册窗口事件处理函数，在 `Input` 处理完事件之后调用
+    ///
+    /// 渲染器创建之前收到的事件不会传给处理函数，`run_headless` 没有窗口，也不会产生事件
+    pub fn add_event_handler(
+        &mut self,
+        handler: impl FnMut(&mut EngineContext, &WindowEvent) + 'static,
+    ) -> &mut Self {
+        self.systems.add_event_handler(Box::new(handler));
+        self
     }
 
     pub fn run(
@@ -30,36 +125,121 @@ impl App {
         event_loop: winit::event_loop::EventLoop<()>, 
         control_flow: ControlFlow
     ) {
+        self.control_flow = control_flow;
+
         event_loop.set_control_flow(control_flow);
         let _ = event_loop.run_app(self);
     }
 
-    fn init_window(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
-        let window_config = &self.window_config.lock();
-    
-        let resolution = Some(match window_config.resolution {
-            ResolutionConfig::Physical(w, h) => Size::Physical(PhysicalSize::new(w, h)),
-            ResolutionConfig::Logical(w, h) => Size::Logical(LogicalSize::new(w as f64, h as f64)),
-        });
-        let min_resolution = Some( match window_config.min_resolution {
-            ResolutionConfig::Physical(w, h) => Size::Physical(PhysicalSize::new(w, h)),
-            ResolutionConfig::Logical(w, h) => Size::Logical(LogicalSize::new(w as f64, h as f64)),
-        });
-    
-        let fullscreen = if window_config.fullscreen {
-            Some(Fullscreen::Borderless(None))
-        } else {
-            None
+    /// 不创建窗口，在离屏纹理上运行 `frames` 帧，返回最后一帧的 RGBA8 像素
+    ///
+    /// 画面尺寸取自 `WindowConfig::resolution`，适合在没有显示器的 CI 上使用
+    pub fn run_headless(&mut self, frames: u32) -> Vec<u8> {
+        self.run_headless_with(frames, None)
+    }
+
+    /// 与 `run_headless` 相同，但每帧的时间固定前进 `delta_time`，结果可重复
+    pub fn run_headless_fixed(&mut self, frames: u32, delta_time: Duration) -> Vec<u8> {
+        self.run_headless_with(frames, Some(delta_time))
+    }
+
+    fn run_headless_with(&mut self, frames: u32, delta_time: Option<Duration>) -> Vec<u8> {
+        let size = {
+            let window_config = self.window_config.lock();
+            PhysicalSize::new(
+                window_config.resolution.width(),
+                window_config.resolution.height(),
+            )
         };
+
+        self.wr = Some(
+            WgpuRenderer::new_headless(Arc::clone(&self.window_config), size)
+                .block_on()
+        );
+
+        self.start();
+
+        for _ in 0..frames {
+            match delta_time {
+                Some(delta_time) => self.timer.step(delta_time),
+                None => {
+                    self.limiter.wait();
+                    self.limiter.begin_frame();
+                    self.timer.update();
+                }
+            }
+
+            self.update_and_render();
+        }
+
+        self.wr
+            .as_ref()
+            .and_then(|wr| wr.read_pixels())
+            .expect("headless renderer must have an offscreen target")
+    }
+
+    fn init_window(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        let window_config = &mut self.window_config.lock();
+
+        let monitors: Vec<_> = event_loop.available_monitors().collect();
+
+        if window_config.remember_window {
+            if let Some(geometry) = WindowGeometry::load() {
+                let primary = event_loop.primary_monitor();
+                geometry.restore(window_config, &monitor_infos(&monitors, primary.as_ref()));
+
+                // 命令行中明确指定的大小和全屏方式优先于上次保存的状态，出错时 with_settings 已经打印过警告
+                let _ = window_config.apply_args(WindowConfig::cli_args());
+
+                self.geometry = geometry;
+            }
+        }
+
+        let fullscreen = resolve_fullscreen(
+            window_config.fullscreen,
+            &monitors,
+            event_loop.primary_monitor(),
+        );
     
         let mut window_attributes = WindowAttributes::default();
     
         window_attributes.title          = window_config.title_name.clone();
-        window_attributes.inner_size     = resolution;
-        window_attributes.min_inner_size = min_resolution;
+        window_attributes.inner_size     = Some(window_config.resolution.to_size());
+        window_attributes.min_inner_size = Some(window_config.min_resolution.to_size());
+        window_attributes.max_inner_size = window_config.max_resolution.map(ResolutionConfig::to_size);
         window_attributes.fullscreen     = fullscreen;
+        window_attributes.maximized      = window_config.maximized;
+        window_attributes.resizable      = window_config.resizable;
+        window_attributes.decorations    = window_config.decorations;
+        window_attributes.transparent    = window_config.transparent;
+        window_attributes.window_level   = window_level(window_config.always_on_top);
+
+        if let WindowPosition::Physical(x, y) = window_config.position {
+            window_attributes.position = Some(Position::Physical(PhysicalPosition::new(x, y)));
+        }
+
+        if let Some(bytes) = window_config.icon {
+            match load_icon(bytes) {
+                Ok(icon) => window_attributes.window_icon = Some(icon),
+                Err(err) => warn!("Failed to load window icon: {err}"),
+            }
+        }
     
-        self.window = Some(Arc::new(event_loop.create_window(window_attributes).unwrap()));
+        let window = event_loop.create_window(window_attributes).unwrap();
+        self.input.set_scale_factor(window.scale_factor());
+
+        // 窗口创建后才知道实际的外框大小
+        if window_config.position == WindowPosition::Centered && window.fullscreen().is_none() {
+            if let Some(monitor) = event_loop.primary_monitor().or_else(|| window.current_monitor()) {
+                window.set_outer_position(centered_position(&monitor, window.outer_size()));
+            }
+        }
+
+        window.set_cursor_visible(window_config.cursor_visible);
+        apply_cursor_grab(&window, window_config.cursor_grab);
+
+        self.geometry.track(&window);
+        self.window = Some(Arc::new(window));
     }
 
     fn init_wr(&mut self) {
@@ -74,22 +254,118 @@ impl App {
 
     fn get_components(&mut self) -> (
         &mut Box<dyn GameLoop>,
-        &Time, &mut WgpuRenderer, 
+        EngineContext<'_>,
     ) {
         (
             &mut self.game,
-            &self.timer,
-            self.wr.as_mut().unwrap()
+            EngineContext {
+                timer: &mut self.timer,
+                input: &mut self.input,
+                limiter: &mut self.limiter,
+                scheduler: &mut self.scheduler,
+                scenes: &mut self.scenes,
+                resources: &mut self.resources,
+                events: &mut self.events,
+                #[cfg(feature = "ecs")]
+                world: &mut self.world,
+                renderer: self.wr.as_mut().unwrap(),
+                window: self.window.as_deref(),
+                window_config: &self.window_config,
+            },
         )
     }
 
+    fn start(&mut self) {
+        let mut systems = std::mem::take(&mut self.systems);
+        let (game, mut c) = self.get_components();
+
+        game.start(&mut c);
+        systems.run(SystemStage::Start, &mut c);
+
+        self.systems = systems;
+    }
+
+    fn frame(&mut self) {
+        self.timer.update();
+        self.update_and_render();
+    }
+
+    fn update_and_render(&mut self) {
+        self.events.update();
+
+        let fixed_steps = self.timer.fixed_steps();
+        let due_tasks = self.scheduler.take_due(self.timer.get_delta_time());
+
+        // 系统需要和 EngineContext 同时借用，先从 App 中取出
+        let mut systems = std::mem::take(&mut self.systems);
+        let (game, mut c) = self.get_components();
+
+        for _ in 0..fixed_steps {
+            game.fixed_update(&mut c);
+            SceneManager::fixed_update(&mut c);
+            systems.run(SystemStage::FixedUpdate, &mut c);
+        }
+
+        for task in due_tasks {
+            let id = task.id();
+
+            // 同一帧里先执行的任务可能取消了后面的任务
+            if c.scheduler.is_cancelled(id) {
+                continue;
+            }
+
+            let task = task.run(&mut c);
+            c.scheduler.finish(id, task);
+        }
+
+        systems.run(SystemStage::PreUpdate, &mut c);
+
+        game.update(&mut c);
+        SceneManager::update(&mut c);
+
+        game.late_update(&mut c);
+        systems.run(SystemStage::PostUpdate, &mut c);
+
+        #[cfg(feature = "ecs")]
+        render_world(&mut c);
+
+        game.render(&mut c);
+        SceneManager::render(&mut c);
+        systems.run(SystemStage::Render, &mut c);
+
+        self.systems = systems;
+
+        self.renderer_update();
+
+        self.input.end_frame();
+    }
+
+    fn save_geometry(&mut self) {
+        let Some(window) = &self.window else {
+            return;
+        };
+
+        let (remember_window, fullscreen) = {
+            let window_config = self.window_config.lock();
+            (window_config.remember_window, window_config.fullscreen)
+        };
+
+        if !remember_window {
+            return;
+        }
+
+        self.geometry.capture_exit(window, fullscreen);
+
+        if let Err(err) = self.geometry.save() {
+            warn!("Failed to save window state: {err}");
+        }
+    }
+
     fn renderer_update(&mut self) {
         if let Some(c) = &mut self.wr {
-            /* c.update();
+            c.update();
             c.draw();
             c.end_frame();
-
-            clear_shader_uniform_table(); */
         }
     }
 }
@@ -99,7 +375,10 @@ impl ApplicationHandler for App {
         fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
             if let Some(window) = &self.window {
                 if let Some(wr) = &mut self.wr {
-                    wr.context.resume(window.clone());
+                    wr.resume(window.clone());
+
+                    let (game, mut c) = self.get_components();
+                    game.on_resume(&mut c);
     
                     info!("Resumed");
                 } else {
@@ -110,29 +389,29 @@ impl ApplicationHandler for App {
                 self.init_wr();
 
                 // 在这里Start
-                {
-                    let (game, timer, renderer) = self.get_components();
-                    let mut c = EngineContext { renderer, timer };
-        
-                    game.start(&mut c);
-                }
+                self.start();
     
                 info!("InitWindow");
             }
         }
     
         // 在事件循环即将等待输入事件时调用
-        fn about_to_wait(&mut self, _: &ActiveEventLoop) {
-            if self.wr.is_some() {
-                self.timer.update();
+        fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+            if self.wr.is_none() {
+                return;
+            }
 
-                let (game, timer, renderer) = self.get_components();
-                let mut c = EngineContext { renderer, timer };
-    
-                game.update(&mut c);
-        
-                self.renderer_update();
+            // 离下一帧还早就先让出 CPU，ControlFlow::Wait 下不会被唤醒，只能原地等待
+            if self.limiter.should_wait(Instant::now()) && self.control_flow != ControlFlow::Wait {
+                event_loop.set_control_flow(self.limiter.control_flow(self.control_flow));
+                return;
             }
+
+            self.limiter.wait();
+            self.limiter.begin_frame();
+            self.frame();
+
+            event_loop.set_control_flow(self.limiter.control_flow(self.control_flow));
         }
     
         // 处理窗口相关的事件
@@ -142,27 +421,81 @@ impl ApplicationHandler for App {
             _: winit::window::WindowId,
             event: winit::event::WindowEvent,
         ) {
+            // 输入使用真实时间，暂停时手势识别仍然正常
+            self.input.handle_window_event(&event, self.timer.get_unscaled_time());
+            self.events.send_window_event(&event);
+
+            // EngineContext 需要渲染器，窗口创建完成之前的事件只交给 Input
+            if self.wr.is_some() {
+                let mut systems = std::mem::take(&mut self.systems);
+                let (_, mut c) = self.get_components();
+
+                systems.handle_event(&mut c, &event);
+
+                self.systems = systems;
+            }
+
+            if let (WindowEvent::Resized(_) | WindowEvent::Moved(_), Some(window)) = (&event, &self.window) {
+                self.geometry.track(window);
+            }
+
             match event {
                 WindowEvent::Resized(new_size) => {
+                    // 全屏、最大化和最小化时的大小不写回配置，恢复窗口模式时仍使用原来的大小
+                    // Windows 上最小化会收到 Resized(0, 0)
+                    if let Some(window) = &self.window {
+                        let minimized = window.is_minimized() == Some(true)
+                            || new_size.width == 0
+                            || new_size.height == 0;
+
+                        if !minimized && window.fullscreen().is_none() && !window.is_maximized() {
+                            self.window_config.lock().resolution =
+                                ResolutionConfig::Physical(new_size.width, new_size.height);
+                        }
+                    }
+
                     if let Some(wr) = &mut self.wr {
                         wr.resize(new_size);
+
+                        let (game, mut c) = self.get_components();
+                        game.on_resize(&mut c, new_size);
     
                         self.about_to_wait(event_loop);
                     }
                 },
+                WindowEvent::Focused(focused) => {
+                    self.limiter.set_focused(focused);
+
+                    if self.wr.is_some() {
+                        let (game, mut c) = self.get_components();
+                        game.on_focus_changed(&mut c, focused);
+                    }
+                },
                 WindowEvent::CloseRequested => {
-                    event_loop.exit();
+                    let close = if self.wr.is_some() {
+                        let (game, mut c) = self.get_components();
+                        game.on_close_requested(&mut c)
+                    } else {
+                        true
+                    };
+
+                    if close {
+                        event_loop.exit();
+                    }
                 },
                 _ => (),
             }
         }
     
-        // region: 看起来没什么用的内容
+        // region: 生命周期
     
         // 当应用程序被挂起时调用
         fn suspended(&mut self, _: &ActiveEventLoop) {
             if let Some(wr) = self.wr.as_mut() {
                 wr.context.surface.take();
+
+                let (game, mut c) = self.get_components();
+                game.on_suspend(&mut c);
             }
             
             info!("Suspended");
@@ -170,13 +503,312 @@ impl ApplicationHandler for App {
     
         // 在应用程序准备退出时调用
         fn exiting(&mut self, _: &ActiveEventLoop) {
+            if self.wr.is_some() {
+                let (game, mut c) = self.get_components();
+                game.on_exit(&mut c);
+            }
+
+            self.save_geometry();
+
             info!("Exiting");
         }
     
-        // endregion: 看起来没什么用的内容
+        // endregion: 生命周期
 }
 
 pub struct EngineContext<'a> {
-    pub timer: &'a Time,
+    pub timer: &'a mut Time,
+    pub input: &'a mut Input,
+    pub limiter: &'a mut FrameLimiter,
+    pub scheduler: &'a mut Scheduler,
+    pub scenes: &'a mut SceneManager,
+    pub resources: &'a mut Resources,
+    pub events: &'a mut Events,
+    #[cfg(feature = "ecs")]
+    pub world: &'a mut World,
     pub renderer: &'a mut WgpuRenderer,
+
+    /// 无窗口模式下为 None
+    window: Option<&'a Window>,
+    window_config: &'a Mutex<WindowConfig>,
+}
+
+/// 设置光标锁定，平台不支持时尝试另一种锁定方式，返回实际使用的模式
+fn apply_cursor_grab(window: &Window, mode: CursorGrabMode) -> CursorGrabMode {
+    let fallback = match mode {
+        CursorGrabMode::Confined => CursorGrabMode::Locked,
+        CursorGrabMode::Locked => CursorGrabMode::Confined,
+        CursorGrabMode::None => CursorGrabMode::None,
+    };
+
+    for mode in [mode, fallback] {
+        match window.set_cursor_grab(mode) {
+            Ok(()) => return mode,
+            Err(err) => warn!("Failed to set cursor grab {mode:?}: {err}"),
+        }
+    }
+
+    CursorGrabMode::None
+}
+
+fn window_level(always_on_top: bool) -> WindowLevel {
+    if always_on_top {
+        WindowLevel::AlwaysOnTop
+    } else {
+        WindowLevel::Normal
+    }
+}
+
+/// 让 `size` 大小的窗口位于显示器中央
+fn centered_position(monitor: &MonitorHandle, size: PhysicalSize<u32>) -> PhysicalPosition<i32> {
+    let position = monitor.position();
+    let monitor_size = monitor.size();
+
+    PhysicalPosition::new(
+        position.x + (monitor_size.width as i32 - size.width as i32) / 2,
+        position.y + (monitor_size.height as i32 - size.height as i32) / 2,
+    )
+}
+
+impl EngineContext<'_> {
+    pub fn window(&self) -> Option<&Window> {
+        self.window
+    }
+
+    /// 当前窗口设置的副本
+    pub fn window_config(&self) -> WindowConfig {
+        self.window_config.lock().clone()
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        if let Some(window) = self.window {
+            window.set_title(title);
+        }
+
+        self.window_config.lock().title_name = title.to_owned();
+    }
+
+    pub fn set_fullscreen(&mut self, mode: FullscreenMode) {
+        if let Some(window) = self.window {
+            let monitors: Vec<_> = window.available_monitors().collect();
+            window.set_fullscreen(resolve_fullscreen(mode, &monitors, window.current_monitor()));
+        }
+
+        self.window_config.lock().fullscreen = mode;
+    }
+
+    /// 在窗口和当前显示器上的无边框全屏之间切换
+    pub fn toggle_fullscreen(&mut self) {
+        let mode = if self.is_fullscreen() {
+            FullscreenMode::Windowed
+        } else {
+            FullscreenMode::Borderless { monitor: None }
+        };
+
+        self.set_fullscreen(mode);
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        match self.window {
+            Some(window) => window.fullscreen().is_some(),
+            None => self.window_config.lock().fullscreen.is_fullscreen(),
+        }
+    }
+
+    /// 最近一次设置的全屏方式
+    pub fn fullscreen_mode(&self) -> FullscreenMode {
+        self.window_config.lock().fullscreen
+    }
+
+    /// 所有可用的显示器，无窗口模式下为空
+    pub fn monitors(&self) -> Vec<MonitorInfo> {
+        let Some(window) = self.window else {
+            return Vec::new();
+        };
+
+        let monitors: Vec<_> = window.available_monitors().collect();
+        monitor_infos(&monitors, window.primary_monitor().as_ref())
+    }
+
+    /// 窗口当前所在的显示器在 `monitors` 中的序号
+    pub fn current_monitor(&self) -> Option<usize> {
+        let window = self.window?;
+        let current = window.current_monitor()?;
+
+        window.available_monitors().position(|monitor| monitor == current)
+    }
+
+    /// 修改窗口大小（物理像素），实际大小由平台决定，改变后会收到 `WindowResized` 事件
+    pub fn set_window_size(&mut self, width: u32, height: u32) {
+        let size = PhysicalSize::new(width.max(1), height.max(1));
+
+        // 有窗口时由 Resized 事件调整渲染器并把实际大小写回配置
+        match self.window {
+            Some(window) => {
+                let _ = window.request_inner_size(size);
+            }
+            None => {
+                self.renderer.resize(size);
+                self.window_config.lock().resolution = ResolutionConfig::Physical(size.width, size.height);
+            }
+        }
+    }
+
+    /// 把窗口移动到桌面上的物理像素位置，部分平台（如 Wayland）不支持
+    pub fn set_window_position(&mut self, x: i32, y: i32) {
+        if let Some(window) = self.window {
+            window.set_outer_position(PhysicalPosition::new(x, y));
+        }
+
+        self.window_config.lock().position = WindowPosition::Physical(x, y);
+    }
+
+    /// 把窗口移动到当前显示器的中央
+    pub fn center_window(&mut self) {
+        if let Some(window) = self.window {
+            if let Some(monitor) = window.current_monitor() {
+                window.set_outer_position(centered_position(&monitor, window.outer_size()));
+            }
+        }
+
+        self.window_config.lock().position = WindowPosition::Centered;
+    }
+
+    pub fn set_resizable(&mut self, resizable: bool) {
+        if let Some(window) = self.window {
+            window.set_resizable(resizable);
+        }
+
+        self.window_config.lock().resizable = resizable;
+    }
+
+    pub fn set_decorations(&mut self, decorations: bool) {
+        if let Some(window) = self.window {
+            window.set_decorations(decorations);
+        }
+
+        self.window_config.lock().decorations = decorations;
+    }
+
+    pub fn set_always_on_top(&mut self, always_on_top: bool) {
+        if let Some(window) = self.window {
+            window.set_window_level(window_level(always_on_top));
+        }
+
+        self.window_config.lock().always_on_top = always_on_top;
+    }
+
+    pub fn set_max_window_size(&mut self, size: Option<ResolutionConfig>) {
+        if let Some(window) = self.window {
+            window.set_max_inner_size(size.map(ResolutionConfig::to_size));
+        }
+
+        self.window_config.lock().max_resolution = size;
+    }
+
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        if let Some(window) = self.window {
+            window.set_cursor_visible(visible);
+        }
+
+        self.window_config.lock().cursor_visible = visible;
+    }
+
+    /// 锁定光标，平台不支持时会尝试另一种锁定方式，返回实际使用的模式
+    pub fn set_cursor_grab(&mut self, mode: CursorGrabMode) -> CursorGrabMode {
+        let mode = match self.window {
+            Some(window) => apply_cursor_grab(window, mode),
+            None => mode,
+        };
+
+        self.window_config.lock().cursor_grab = mode;
+
+        mode
+    }
+
+    /// 修改窗口图标，`icon` 为 PNG 等编码后的图片数据，解码失败时不做任何修改
+    pub fn set_window_icon(&mut self, icon: Option<&'static [u8]>) -> image::ImageResult<()> {
+        let decoded = icon.map(load_icon).transpose()?;
+
+        if let Some(window) = self.window {
+            window.set_window_icon(decoded);
+        }
+
+        self.window_config.lock().icon = icon;
+
+        Ok(())
+    }
+
+    /// 修改呈现模式，在下一帧重新配置 surface
+    pub fn set_present_mode(&mut self, present_mode: PresentMode) {
+        self.window_config.lock().vsync_mode = present_mode;
+    }
+
+    pub fn set_vsync(&mut self, enabled: bool) {
+        self.set_present_mode(if enabled {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        });
+    }
+
+    pub fn clear_background(&mut self, color: Color) {
+        self.renderer.clear_background(color);
+    }
+
+    pub fn set_camera(&mut self, camera: Camera2D) -> Arc<Mutex<Camera2D>> {
+        self.renderer.set_main_camera(camera)
+    }
+
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.renderer.screen_to_world(point)
+    }
+
+    pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
+        self.renderer.world_to_screen(point)
+    }
+
+    pub fn load_texture(
+        &self,
+        bytes: &[u8],
+        filter: FilterMode,
+    ) -> image::ImageResult<Texture2D> {
+        self.renderer.load_texture(bytes, filter)
+    }
+
+    pub fn draw_texture(&mut self, texture: &Texture2D, x: f32, y: f32, color: Color) {
+        self.renderer.draw_texture(texture, x, y, color);
+    }
+
+    pub fn draw_texture_ex(
+        &mut self,
+        texture: &Texture2D,
+        x: f32,
+        y: f32,
+        color: Color,
+        params: DrawTextureParams,
+    ) {
+        self.renderer.draw_texture_ex(texture, x, y, color, params);
+    }
+
+    pub fn draw_rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.renderer.draw_rectangle(x, y, w, h, color);
+    }
+
+    pub fn draw_circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        self.renderer.draw_circle(x, y, r, color);
+    }
+
+    pub fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        self.renderer.draw_line(x1, y1, x2, y2, thickness, color);
+    }
+
+    pub fn draw_triangle(&mut self, v1: Vec2, v2: Vec2, v3: Vec2, color: Color) {
+        self.renderer.draw_triangle(v1, v2, v3, color);
+    }
+
+    /// `rotation` 为角度（度），与使用弧度的 `DrawTextureParams::rotation` 不同
+    pub fn draw_poly(&mut self, x: f32, y: f32, sides: u8, radius: f32, rotation: f32, color: Color) {
+        self.renderer.draw_poly(x, y, sides, radius, rotation, color);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,154 @@
+use crate::*;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Pod, Zeroable)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub tex_coords: [f32; 2],
+    pub color: [f32; 4],
+}
+
+impl Vertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 3] = wgpu::vertex_attr_array![
+        0 => Float32x3,
+        1 => Float32x2,
+        2 => Float32x4,
+    ];
+
+    pub fn new(x: f32, y: f32, u: f32, v: f32, color: Color) -> Self {
+        Self {
+            position: [x, y, 0.0],
+            tex_coords: [u, v],
+            color: color.into(),
+        }
+    }
+
+    pub(crate) fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+struct DrawCall {
+    texture: Texture2D,
+    index_start: u32,
+    index_count: u32,
+}
+
+/// 按纹理合批的几何缓冲，每帧收集顶点后一次性上传并绘制
+pub struct SpriteBatch {
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+    draw_calls: Vec<DrawCall>,
+
+    vertex_buffer: Buffer,
+    index_buffer: Buffer,
+}
+
+impl SpriteBatch {
+    const INITIAL_VERTICES: usize = 4096;
+
+    pub fn new(device: &Device) -> Self {
+        Self {
+            vertices: Vec::with_capacity(Self::INITIAL_VERTICES),
+            indices: Vec::with_capacity(Self::INITIAL_VERTICES * 3 / 2),
+            draw_calls: Vec::new(),
+
+            vertex_buffer: create_vertex_buffer(device, Self::INITIAL_VERTICES),
+            index_buffer: create_index_buffer(device, Self::INITIAL_VERTICES * 3 / 2),
+        }
+    }
+
+    /// 加入一组几何体，`indices` 相对于本次传入的 `vertices`
+    pub fn push(&mut self, texture: &Texture2D, vertices: &[Vertex], indices: &[u32]) {
+        let base = self.vertices.len() as u32;
+        let index_start = self.indices.len() as u32;
+
+        self.vertices.extend_from_slice(vertices);
+        self.indices.extend(indices.iter().map(|i| base + i));
+
+        // 与上一次绘制使用同一纹理时直接合并
+        match self.draw_calls.last_mut() {
+            Some(last) if last.texture.same_texture(texture) => {
+                last.index_count += indices.len() as u32;
+            }
+            _ => self.draw_calls.push(DrawCall {
+                texture: texture.clone(),
+                index_start,
+                index_count: indices.len() as u32,
+            }),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.draw_calls.is_empty()
+    }
+
+    /// 把本帧的顶点和索引写入 GPU，缓冲不够大时重新创建
+    pub(crate) fn upload(&mut self, device: &Device, queue: &Queue) {
+        if self.is_empty() {
+            return;
+        }
+
+        let vertex_capacity = self.vertex_buffer.size() as usize / std::mem::size_of::<Vertex>();
+        if self.vertices.len() > vertex_capacity {
+            self.vertex_buffer =
+                create_vertex_buffer(device, self.vertices.len().next_power_of_two());
+        }
+
+        let index_capacity = self.index_buffer.size() as usize / std::mem::size_of::<u32>();
+        if self.indices.len() > index_capacity {
+            self.index_buffer =
+                create_index_buffer(device, self.indices.len().next_power_of_two());
+        }
+
+        queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&self.vertices));
+        queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&self.indices));
+    }
+
+    /// 在已经设置好管线和相机的渲染通道里提交所有绘制
+    pub(crate) fn flush(&self, pass: &mut wgpu::RenderPass) {
+        if self.is_empty() {
+            return;
+        }
+
+        pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+
+        for call in &self.draw_calls {
+            pass.set_bind_group(1, &*call.texture.bind_group(), &[]);
+            pass.draw_indexed(
+                call.index_start..call.index_start + call.index_count,
+                0,
+                0..1,
+            );
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.draw_calls.clear();
+    }
+}
+
+fn create_vertex_buffer(device: &Device, capacity: usize) -> Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("sprite_vertex_buffer"),
+        size: (capacity * std::mem::size_of::<Vertex>()) as wgpu::BufferAddress,
+        usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+fn create_index_buffer(device: &Device, capacity: usize) -> Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("sprite_index_buffer"),
+        size: (capacity * std::mem::size_of::<u32>()) as wgpu::BufferAddress,
+        usage: BufferUsages::INDEX | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
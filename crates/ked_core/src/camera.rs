@@ -0,0 +1,139 @@
+use crate::*;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub(crate) struct CameraUniform {
+    view_proj: [[f32; 4]; 4],
+}
+
+impl CameraUniform {
+    pub fn new(view_proj: Mat4) -> Self {
+        Self {
+            view_proj: view_proj.to_cols_array_2d(),
+        }
+    }
+}
+
+/// 相机缩放的下限，缩放为 0 或负数时矩阵不可逆
+pub const MIN_ZOOM: f32 = 1e-4;
+
+/// 2D 相机，屏幕坐标使用物理像素，左上角为原点，y 轴向下
+#[derive(Debug, Clone)]
+pub struct Camera2D {
+    /// 相机看向的世界坐标，对应视口中心
+    pub position: Vec2,
+    /// 缩放，1.0 时一个世界单位等于一个像素，小于 `MIN_ZOOM` 时按 `MIN_ZOOM` 计算
+    pub zoom: f32,
+    /// 旋转角度（弧度）
+    pub rotation: f32,
+    /// 相机绘制到屏幕上的区域（像素），`None` 为整个窗口
+    pub viewport: Option<Rect>,
+
+    screen_size: Vec2,
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+            viewport: None,
+
+            screen_size: Vec2::ONE,
+        }
+    }
+}
+
+impl Camera2D {
+    pub fn new(position: Vec2, zoom: f32) -> Self {
+        Self {
+            position,
+            zoom: zoom.max(MIN_ZOOM),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.max(MIN_ZOOM);
+    }
+
+    /// 窗口尺寸改变时由渲染器调用
+    pub fn resize(&mut self, new_size: PhysicalSize<u32>) {
+        self.screen_size = vec2(new_size.width as f32, new_size.height as f32);
+    }
+
+    /// 实际使用的视口，会被限制在窗口范围内
+    pub fn viewport_rect(&self) -> Rect {
+        let Some(viewport) = self.viewport else {
+            return Rect::new(0.0, 0.0, self.screen_size.x, self.screen_size.y);
+        };
+
+        let x = viewport.x.clamp(0.0, self.screen_size.x - 1.0);
+        let y = viewport.y.clamp(0.0, self.screen_size.y - 1.0);
+
+        Rect::new(
+            x,
+            y,
+            viewport.w.clamp(1.0, self.screen_size.x - x),
+            viewport.h.clamp(1.0, self.screen_size.y - y),
+        )
+    }
+
+    /// 世界坐标到屏幕像素坐标的变换
+    pub fn view_matrix(&self) -> Mat4 {
+        let viewport = self.viewport_rect();
+        let center = vec2(viewport.x + viewport.w / 2.0, viewport.y + viewport.h / 2.0);
+        let zoom = self.zoom.max(MIN_ZOOM);
+
+        Mat4::from_translation(center.extend(0.0))
+            * Mat4::from_scale(Vec3::new(zoom, zoom, 1.0))
+            * Mat4::from_rotation_z(-self.rotation)
+            * Mat4::from_translation((-self.position).extend(0.0))
+    }
+
+    /// 传给着色器的 view-projection 矩阵，渲染通道的视口需要设置为 `viewport_rect`
+    pub fn view_proj(&self) -> Mat4 {
+        let viewport = self.viewport_rect();
+
+        let projection = Mat4::orthographic_rh(
+            viewport.x,
+            viewport.x + viewport.w,
+            viewport.y + viewport.h,
+            viewport.y,
+            -1.0,
+            1.0,
+        );
+
+        projection * self.view_matrix()
+    }
+
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.view_matrix().inverse().transform_point3(point.extend(0.0)).truncate()
+    }
+
+    pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
+        self.view_matrix().transform_point3(point.extend(0.0)).truncate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_zoom_keeps_the_matrix_invertible() {
+        let mut camera = Camera2D::new(vec2(10.0, 20.0), 0.0);
+        camera.resize(PhysicalSize::new(800, 600));
+
+        assert_eq!(camera.zoom, MIN_ZOOM);
+
+        camera.zoom = -2.0;
+        let world = camera.screen_to_world(vec2(400.0, 300.0));
+        assert!(world.is_finite());
+        assert!((camera.world_to_screen(world) - vec2(400.0, 300.0)).length() < 1e-2);
+
+        camera.set_zoom(0.0);
+        assert_eq!(camera.zoom, MIN_ZOOM);
+    }
+}
@@ -7,7 +7,7 @@ use crate::*;
 /// Color types and helpers.
 /// A color represented by 4 floats: red, green, blue and alpha.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Color {
     /// Red channel value from 0.0 to 1.0
     pub r: f32,
@@ -60,31 +60,31 @@ macro_rules! color_u8 {
     };
 }
 
-impl Into<[u8; 4]> for Color {
-    fn into(self) -> [u8; 4] {
+impl From<Color> for [u8; 4] {
+    fn from(val: Color) -> Self {
         [
-            (self.r * 255.) as u8,
-            (self.g * 255.) as u8,
-            (self.b * 255.) as u8,
-            (self.a * 255.) as u8,
+            (val.r * 255.) as u8,
+            (val.g * 255.) as u8,
+            (val.b * 255.) as u8,
+            (val.a * 255.) as u8,
         ]
     }
 }
 
-impl Into<Color> for [u8; 4] {
-    fn into(self) -> Color {
+impl From<[u8; 4]> for Color {
+    fn from(val: [u8; 4]) -> Self {
         Color::new(
-            self[0] as f32 / 255.,
-            self[1] as f32 / 255.,
-            self[2] as f32 / 255.,
-            self[3] as f32 / 255.,
+            val[0] as f32 / 255.,
+            val[1] as f32 / 255.,
+            val[2] as f32 / 255.,
+            val[3] as f32 / 255.,
         )
     }
 }
 
-impl Into<[f32; 4]> for Color {
-    fn into(self) -> [f32; 4] {
-        [self.r, self.g, self.b, self.a]
+impl From<Color> for [f32; 4] {
+    fn from(val: Color) -> Self {
+        [val.r, val.g, val.b, val.a]
     }
 }
 
@@ -112,7 +112,7 @@ impl Color {
     /// # Example
     ///
     /// ```
-    /// use macroquad::prelude::*;
+    /// use ked_core::*;
     ///
     /// let pink = Color::new(1.00, 0.43, 0.76, 1.00);
     /// assert_eq!(pink.r, 1.00);
@@ -144,7 +144,7 @@ impl Color {
     /// # Example
     ///
     /// ```
-    /// use macroquad::prelude::*;
+    /// use ked_core::*;
     ///
     /// let light_blue = Color::from_hex(0x3CA7D5);
     /// assert_eq!(light_blue.r, 0.23529412);
@@ -158,6 +158,11 @@ impl Color {
         Self::from_rgba(bytes[1], bytes[2], bytes[3], 255)
     }
 
+    /// Multiply the red, green and blue components by alpha.
+    pub const fn premultiplied(&self) -> Color {
+        Color::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
     /// Create a vec4 of red, green, blue, and alpha components.
     pub const fn to_vec(&self) -> Vec4 {
         vec4(self.r, self.g, self.b, self.a)
@@ -245,9 +250,9 @@ pub fn rgb_to_hsl(color: Color) -> (f32, f32, f32) {
     };
 
     // Fix wraparounds
-    if h < 0 as f32 {
+    if h < 0.0 {
         h += 1.0;
-    } else if h > 1 as f32 {
+    } else if h > 1.0 {
         h -= 1.0;
     }
 
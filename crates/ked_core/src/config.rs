@@ -4,7 +4,20 @@
 
 use crate::*;
 
-#[derive(Copy, Clone, Debug)]
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::OnceCell;
+
+/// 按顺序查找的配置文件名
+pub const SETTINGS_FILE_NAMES: [&str; 2] = ["settings.toml", "settings.ron"];
+
+static SETTINGS_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// `with_settings` 读取的命令行参数，恢复窗口状态之后需要再次应用
+static CLI_ARGS: OnceCell<Vec<String>> = OnceCell::new();
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ResolutionConfig {
     Physical(u32, u32),
     Logical(u32, u32),
@@ -25,6 +38,13 @@ impl ResolutionConfig {
         }
     }
 
+    pub(crate) fn to_size(self) -> Size {
+        match self {
+            Self::Physical(w, h) => Size::Physical(PhysicalSize::new(w, h)),
+            Self::Logical(w, h) => Size::Logical(LogicalSize::new(w as f64, h as f64)),
+        }
+    }
+
     pub fn ensure_non_zero(&mut self) -> ResolutionConfig {
         const MIN_WINDOW_SIZE: u32 = 1;
         match self {
@@ -42,7 +62,89 @@ impl ResolutionConfig {
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
+/// 窗口的全屏方式
+///
+/// `monitor` 为 `EngineContext::monitors` 中的序号，`None` 表示窗口当前所在的显示器
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FullscreenMode {
+    #[default]
+    Windowed,
+    /// 无边框全屏，使用桌面的分辨率
+    Borderless { monitor: Option<usize> },
+    /// 独占全屏，`video_mode` 为 `None` 或显示器不支持时使用最高的显示模式
+    Exclusive {
+        monitor: Option<usize>,
+        video_mode: Option<VideoMode>,
+    },
+}
+
+impl FullscreenMode {
+    pub fn is_fullscreen(&self) -> bool {
+        *self != Self::Windowed
+    }
+
+    pub fn monitor(&self) -> Option<usize> {
+        match self {
+            Self::Windowed => None,
+            Self::Borderless { monitor } | Self::Exclusive { monitor, .. } => *monitor,
+        }
+    }
+
+    /// 修改全屏使用的显示器，窗口模式下不变
+    pub fn with_monitor(self, monitor: Option<usize>) -> Self {
+        match self {
+            Self::Windowed => self,
+            Self::Borderless { .. } => Self::Borderless { monitor },
+            Self::Exclusive { video_mode, .. } => Self::Exclusive { monitor, video_mode },
+        }
+    }
+}
+
+/// 兼容旧配置文件中的 `fullscreen = true / false`，`true` 为当前显示器上的无边框全屏
+///
+/// RON 中的 `true` / `false` 按枚举成员名读取，TOML 中的布尔值由 `from_toml_str` 先转换成字符串
+fn deserialize_fullscreen<'de, D>(deserializer: D) -> Result<FullscreenMode, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(rename = "FullscreenMode")]
+    enum FullscreenSetting {
+        #[serde(rename = "true")]
+        On,
+        #[serde(rename = "false")]
+        Off,
+        Windowed,
+        Borderless {
+            monitor: Option<usize>,
+        },
+        Exclusive {
+            monitor: Option<usize>,
+            video_mode: Option<VideoMode>,
+        },
+    }
+
+    Ok(match FullscreenSetting::deserialize(deserializer)? {
+        FullscreenSetting::On => FullscreenMode::Borderless { monitor: None },
+        FullscreenSetting::Off | FullscreenSetting::Windowed => FullscreenMode::Windowed,
+        FullscreenSetting::Borderless { monitor } => FullscreenMode::Borderless { monitor },
+        FullscreenSetting::Exclusive { monitor, video_mode } => FullscreenMode::Exclusive { monitor, video_mode },
+    })
+}
+
+/// 窗口创建时的位置
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowPosition {
+    /// 由平台决定
+    #[default]
+    Automatic,
+    /// 主显示器的中央
+    Centered,
+    /// 窗口左上角在桌面上的物理像素位置
+    Physical(i32, i32),
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Msaa {
     Off = 1,
     Sample2 = 2,
@@ -58,18 +160,51 @@ impl From<Msaa> for u32 {
     }
 }
 
-#[derive(Debug, Clone)]
+/// 窗口和渲染设置，可以从配置文件读取，缺少的字段使用默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WindowConfig {
     pub title_name: String,
+    /// 游戏版本，只能在代码中设置，不会写入配置文件
+    #[serde(skip)]
     pub version: &'static str,
-    pub fullscreen: bool,
+    /// 窗口图标，PNG 等图片文件的内容，通常使用 `include_bytes!`，不会写入配置文件
+    #[serde(skip)]
+    pub icon: Option<&'static [u8]>,
+    #[serde(deserialize_with = "deserialize_fullscreen")]
+    pub fullscreen: FullscreenMode,
 
     pub resolution: ResolutionConfig,
     pub min_resolution: ResolutionConfig,
+    pub max_resolution: Option<ResolutionConfig>,
+    pub position: WindowPosition,
+
+    pub maximized: bool,
+    pub resizable: bool,
+    pub decorations: bool,
+    pub always_on_top: bool,
+    /// 透明窗口，`clear_color` 的 alpha 决定背景的透明度，需要平台和显卡支持
+    pub transparent: bool,
+    /// 退出时保存窗口大小、位置和全屏状态，下次启动时恢复，见 `WindowGeometry`
+    pub remember_window: bool,
     
     pub sample_count: Msaa,
     pub vsync_mode: PresentMode,
     pub power_preference: PowerPreference,
+    pub force_fallback_adapter: bool,
+
+    /// fixed_update 每秒执行的次数
+    pub fixed_update_rate: u32,
+    /// 每帧最多追赶的 fixed_update 次数
+    pub max_fixed_steps: u32,
+
+    /// 帧率上限，`None` 表示不限制（仍受 `vsync_mode` 影响）
+    pub target_fps: Option<u32>,
+    /// 窗口失去焦点时的帧率上限，`None` 表示与 `target_fps` 相同
+    pub background_fps: Option<u32>,
+
+    pub cursor_visible: bool,
+    pub cursor_grab: CursorGrabMode,
 
     pub clear_color: Color
 }
@@ -79,25 +214,505 @@ impl Default for WindowConfig {
         Self { 
             title_name: "New Game".to_owned(),
             version: "New Version",
-            fullscreen: false,
+            icon: None,
+            fullscreen: FullscreenMode::Windowed,
 
             resolution: ResolutionConfig::Physical(1280, 720), 
             min_resolution: ResolutionConfig::Physical(100, 100), 
+            max_resolution: None,
+            position: WindowPosition::Automatic,
+
+            maximized: false,
+            resizable: true,
+            decorations: true,
+            always_on_top: false,
+            transparent: false,
+            remember_window: !cfg!(target_os = "android"),
 
             sample_count: Msaa::default(),
             vsync_mode: PresentMode::default(),
             power_preference: PowerPreference::default(),
+            force_fallback_adapter: false,
+
+            fixed_update_rate: 60,
+            max_fixed_steps: 5,
+
+            target_fps: None,
+            background_fps: Some(30),
+
+            cursor_visible: true,
+            cursor_grab: CursorGrabMode::None,
 
             clear_color: BLUE
         }
     }
 }
-/* 
-pub fn clear_background(color: Color) {
-    let mut last_config = WINDOW_CONFIG
-        .get()
-        .expect("window_config() must be called after comfy main runs")
-        .borrow_mut();
 
-    last_config.clear_color = color;
-} */
+impl WindowConfig {
+    pub fn from_toml_str(source: &str) -> Result<Self, ConfigError> {
+        let mut table: toml::Table = toml::from_str(source)?;
+
+        // TOML 的布尔值不能按枚举读取，见 deserialize_fullscreen
+        if let Some(toml::Value::Boolean(fullscreen)) = table.get("fullscreen") {
+            let fullscreen = fullscreen.to_string();
+            table.insert("fullscreen".to_owned(), toml::Value::String(fullscreen));
+        }
+
+        Ok(table.try_into()?)
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, ConfigError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    pub fn from_ron_str(source: &str) -> Result<Self, ConfigError> {
+        Ok(ron::from_str(source)?)
+    }
+
+    pub fn to_ron_string(&self) -> Result<String, ConfigError> {
+        Ok(ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?)
+    }
+
+    /// 读取配置文件，扩展名为 `.ron` 时按 RON 解析，否则按 TOML 解析
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)?;
+
+        if is_ron(path) {
+            Self::from_ron_str(&source)
+        } else {
+            Self::from_toml_str(&source)
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ConfigError> {
+        let path = path.as_ref();
+        let source = if is_ron(path) {
+            self.to_ron_string()?
+        } else {
+            self.to_toml_string()?
+        };
+
+        std::fs::write(path, source)?;
+
+        Ok(())
+    }
+
+    /// 修改查找配置文件的目录，只有第一次调用生效
+    ///
+    /// Android 上由 `ked_main` 设置为应用的内部数据目录
+    pub fn set_settings_dir(dir: impl Into<PathBuf>) {
+        let _ = SETTINGS_DIR.set(dir.into());
+    }
+
+    /// 查找配置文件的目录，默认为可执行文件所在的目录
+    pub fn settings_dir() -> Option<PathBuf> {
+        if let Some(dir) = SETTINGS_DIR.get() {
+            return Some(dir.clone());
+        }
+
+        std::env::current_exe().ok()?.parent().map(Path::to_path_buf)
+    }
+
+    /// 保存运行时状态（如窗口位置）的目录，`set_settings_dir` 设置过时与 `settings_dir` 相同
+    ///
+    /// 否则为用户配置目录下以可执行文件名命名的目录，安装目录通常没有写入权限
+    pub fn user_settings_dir() -> Option<PathBuf> {
+        if let Some(dir) = SETTINGS_DIR.get() {
+            return Some(dir.clone());
+        }
+
+        let name = std::env::current_exe().ok()?.file_stem()?.to_owned();
+        let env_dir = |name: &str| std::env::var_os(name).filter(|dir| !dir.is_empty()).map(PathBuf::from);
+
+        let base = if cfg!(target_os = "windows") {
+            env_dir("APPDATA")
+        } else if cfg!(target_os = "macos") {
+            env_dir("HOME").map(|home| home.join("Library/Application Support"))
+        } else {
+            env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+        };
+
+        Some(base?.join(name))
+    }
+
+    /// 配置目录中第一个存在的配置文件
+    pub fn settings_path() -> Option<PathBuf> {
+        let dir = Self::settings_dir()?;
+
+        SETTINGS_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    /// 读取配置文件（`--config <path>` 或配置目录中的 settings.toml / settings.ron），
+    /// 再应用命令行参数。没有配置文件时保留 `self` 的设置，出错时只打印警告
+    ///
+    /// ```no_run
+    /// use ked_core::*;
+    ///
+    /// let window_config = WindowConfig {
+    ///     title_name: "My Game".to_owned(),
+    ///     ..Default::default()
+    /// }
+    /// .with_settings();
+    /// ```
+    pub fn with_settings(self) -> Self {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+
+        let path = match find_arg(&args, "--config") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => Self::settings_path(),
+        };
+
+        let mut config = match path {
+            Some(path) => match Self::load(&path) {
+                Ok(config) => {
+                    info!("Loaded settings from {}", path.display());
+
+                    Self {
+                        version: self.version,
+                        icon: self.icon,
+                        ..config
+                    }
+                }
+                Err(err) => {
+                    warn!("Failed to load settings from {}: {err}", path.display());
+                    self
+                }
+            },
+            None => self,
+        };
+
+        if let Err(err) = config.apply_args(&args) {
+            warn!("{err}");
+        }
+
+        let _ = CLI_ARGS.set(args);
+
+        config
+    }
+
+    /// `with_settings` 读取的命令行参数，没有调用过时为空
+    pub(crate) fn cli_args() -> &'static [String] {
+        CLI_ARGS.get().map_or(&[], Vec::as_slice)
+    }
+
+    /// 应用命令行参数，不认识的参数会被忽略
+    ///
+    /// 支持 `--fullscreen`、`--exclusive`、`--windowed`、`--monitor <n>`、
+    /// `--resolution 1920x1080`（物理像素）、`--logical-resolution 1280x720`（逻辑像素）、
+    /// `--vsync on|off|<PresentMode>`、`--msaa 1|2|4|8`、`--fps <n>|off`，
+    /// 值也可以写成 `--resolution=1920x1080`，`--monitor` 在窗口模式下返回错误
+    ///
+    /// 出错时 `self` 保持不变
+    pub fn apply_args<S: AsRef<str>>(&mut self, args: &[S]) -> Result<(), ConfigError> {
+        let mut config = self.clone();
+        config.apply_args_in_place(args)?;
+
+        *self = config;
+
+        Ok(())
+    }
+
+    fn apply_args_in_place<S: AsRef<str>>(&mut self, args: &[S]) -> Result<(), ConfigError> {
+        let mut args = args.iter().map(AsRef::as_ref);
+        // 与 --fullscreen 等参数的先后顺序无关，全部处理完之后再应用
+        let mut monitor = None;
+
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (arg, None),
+            };
+
+            let mut value = || {
+                inline_value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ConfigError::InvalidArgument(format!("{name} requires a value")))
+            };
+
+            match name {
+                "--fullscreen" => {
+                    self.fullscreen = FullscreenMode::Borderless {
+                        monitor: self.fullscreen.monitor(),
+                    }
+                }
+                "--exclusive" => {
+                    self.fullscreen = FullscreenMode::Exclusive {
+                        monitor: self.fullscreen.monitor(),
+                        video_mode: None,
+                    }
+                }
+                "--windowed" => self.fullscreen = FullscreenMode::Windowed,
+                "--monitor" => monitor = Some(parse_monitor(value()?)?),
+                "--resolution" => self.resolution = parse_resolution(name, value()?, ResolutionConfig::Physical)?,
+                "--logical-resolution" => {
+                    self.resolution = parse_resolution(name, value()?, ResolutionConfig::Logical)?
+                }
+                "--vsync" => self.vsync_mode = parse_present_mode(value()?)?,
+                "--msaa" => self.sample_count = parse_msaa(value()?)?,
+                "--fps" => self.target_fps = parse_fps(value()?)?,
+                // 由 with_settings 处理
+                "--config" => {
+                    value()?;
+                }
+                _ => debug!("Ignoring unknown argument {arg}"),
+            }
+        }
+
+        if let Some(monitor) = monitor {
+            if !self.fullscreen.is_fullscreen() {
+                return Err(ConfigError::InvalidArgument(
+                    "--monitor requires --fullscreen or --exclusive".to_owned(),
+                ));
+            }
+
+            self.fullscreen = self.fullscreen.with_monitor(Some(monitor));
+        }
+
+        Ok(())
+    }
+}
+
+fn is_ron(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ron"))
+}
+
+fn find_arg<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == name {
+            return iter.next().map(String::as_str);
+        }
+
+        if let Some(value) = arg.strip_prefix(name).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+fn invalid(name: &str, value: &str) -> ConfigError {
+    ConfigError::InvalidArgument(format!("invalid value for {name}: {value}"))
+}
+
+/// 解析 `1920x1080` 形式的宽高，`size` 决定使用物理像素还是逻辑像素
+fn parse_resolution(
+    name: &str,
+    value: &str,
+    size: fn(u32, u32) -> ResolutionConfig,
+) -> Result<ResolutionConfig, ConfigError> {
+    let (w, h) = value
+        .split_once(['x', 'X'])
+        .ok_or_else(|| invalid(name, value))?;
+
+    match (w.trim().parse(), h.trim().parse()) {
+        (Ok(w), Ok(h)) if w > 0 && h > 0 => Ok(size(w, h)),
+        _ => Err(invalid(name, value)),
+    }
+}
+
+fn parse_present_mode(value: &str) -> Result<PresentMode, ConfigError> {
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "on" | "true" | "auto" | "autovsync" => PresentMode::AutoVsync,
+        "off" | "false" | "autonovsync" => PresentMode::AutoNoVsync,
+        "fifo" => PresentMode::Fifo,
+        "fiforelaxed" => PresentMode::FifoRelaxed,
+        "mailbox" => PresentMode::Mailbox,
+        "immediate" => PresentMode::Immediate,
+        _ => return Err(invalid("--vsync", value)),
+    })
+}
+
+fn parse_msaa(value: &str) -> Result<Msaa, ConfigError> {
+    Ok(match value {
+        "1" | "off" => Msaa::Off,
+        "2" => Msaa::Sample2,
+        "4" => Msaa::Sample4,
+        "8" => Msaa::Sample8,
+        _ => return Err(invalid("--msaa", value)),
+    })
+}
+
+fn parse_monitor(value: &str) -> Result<usize, ConfigError> {
+    value.parse().map_err(|_| invalid("--monitor", value))
+}
+
+fn parse_fps(value: &str) -> Result<Option<u32>, ConfigError> {
+    match value {
+        "off" | "0" => Ok(None),
+        _ => value.parse().map(Some).map_err(|_| invalid("--fps", value)),
+    }
+}
+
+/// 读写配置文件时的错误
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(toml::de::Error),
+    Serialize(toml::ser::Error),
+    ParseRon(ron::error::SpannedError),
+    SerializeRon(ron::Error),
+    /// 命令行参数格式错误
+    InvalidArgument(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "config io error: {err}"),
+            Self::Parse(err) => write!(f, "config parse error: {err}"),
+            Self::Serialize(err) => write!(f, "config serialize error: {err}"),
+            Self::ParseRon(err) => write!(f, "config parse error: {err}"),
+            Self::SerializeRon(err) => write!(f, "config serialize error: {err}"),
+            Self::InvalidArgument(err) => write!(f, "invalid argument: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<toml::ser::Error> for ConfigError {
+    fn from(err: toml::ser::Error) -> Self {
+        Self::Serialize(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for ConfigError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::ParseRon(err)
+    }
+}
+
+impl From<ron::Error> for ConfigError {
+    fn from(err: ron::Error) -> Self {
+        Self::SerializeRon(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_and_ron_round_trip() {
+        let config = WindowConfig {
+            title_name: "Test".to_owned(),
+            fullscreen: FullscreenMode::Exclusive {
+                monitor: Some(1),
+                video_mode: Some(VideoMode {
+                    width: 1920,
+                    height: 1080,
+                    refresh_rate_millihertz: 144000,
+                    bit_depth: 32,
+                }),
+            },
+            resolution: ResolutionConfig::Logical(800, 600),
+            max_resolution: Some(ResolutionConfig::Physical(1920, 1080)),
+            position: WindowPosition::Physical(-100, 50),
+            transparent: true,
+            vsync_mode: PresentMode::Immediate,
+            target_fps: Some(144),
+            clear_color: RED,
+            ..Default::default()
+        };
+
+        for parsed in [
+            WindowConfig::from_toml_str(&config.to_toml_string().unwrap()).unwrap(),
+            WindowConfig::from_ron_str(&config.to_ron_string().unwrap()).unwrap(),
+        ] {
+            assert_eq!(parsed.title_name, "Test");
+            assert_eq!(parsed.fullscreen, config.fullscreen);
+            assert_eq!(parsed.resolution, ResolutionConfig::Logical(800, 600));
+            assert_eq!(parsed.max_resolution, config.max_resolution);
+            assert_eq!(parsed.position, config.position);
+            assert!(parsed.transparent);
+            assert_eq!(parsed.vsync_mode, PresentMode::Immediate);
+            assert_eq!(parsed.target_fps, Some(144));
+            assert_eq!(parsed.clear_color, RED);
+        }
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let config = WindowConfig::from_toml_str("fullscreen = { Borderless = { monitor = 1 } }").unwrap();
+
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless { monitor: Some(1) });
+        assert_eq!(config.title_name, WindowConfig::default().title_name);
+        assert_eq!(config.sample_count, Msaa::default());
+    }
+
+    #[test]
+    fn cli_overrides() {
+        let mut config = WindowConfig::default();
+
+        config
+            .apply_args(&["--fullscreen", "--resolution", "1920x1080", "--vsync=off", "--unknown", "--fps", "30"])
+            .unwrap();
+
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless { monitor: None });
+        assert_eq!(config.resolution, ResolutionConfig::Physical(1920, 1080));
+        assert_eq!(config.vsync_mode, PresentMode::AutoNoVsync);
+        assert_eq!(config.target_fps, Some(30));
+
+        assert!(config.apply_args(&["--resolution", "big"]).is_err());
+        assert!(config.apply_args(&["--vsync"]).is_err());
+
+        config.apply_args(&["--monitor", "1", "--exclusive"]).unwrap();
+        assert_eq!(config.fullscreen, FullscreenMode::Exclusive { monitor: Some(1), video_mode: None });
+
+        config.apply_args(&["--fullscreen", "--monitor=2"]).unwrap();
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless { monitor: Some(2) });
+
+        // 窗口模式下无法使用 --monitor
+        assert!(config.apply_args(&["--windowed", "--monitor=2"]).is_err());
+
+        config.apply_args(&["--logical-resolution", "1280x720"]).unwrap();
+        assert_eq!(config.resolution, ResolutionConfig::Logical(1280, 720));
+    }
+
+    #[test]
+    fn failed_args_leave_config_unchanged() {
+        let mut config = WindowConfig::default();
+
+        assert!(config.apply_args(&["--resolution", "800x600", "--fps", "30", "--msaa", "3"]).is_err());
+        assert!(config.apply_args(&["--resolution", "800x600", "--monitor", "1"]).is_err());
+
+        assert_eq!(config.resolution, WindowConfig::default().resolution);
+        assert_eq!(config.target_fps, WindowConfig::default().target_fps);
+    }
+
+    #[test]
+    fn fullscreen_accepts_bool() {
+        let config = WindowConfig::from_toml_str("fullscreen = true").unwrap();
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless { monitor: None });
+
+        let config = WindowConfig::from_toml_str("fullscreen = false").unwrap();
+        assert_eq!(config.fullscreen, FullscreenMode::Windowed);
+
+        let config = WindowConfig::from_ron_str("(fullscreen: true)").unwrap();
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless { monitor: None });
+
+        let config = WindowConfig::from_ron_str("(fullscreen: false)").unwrap();
+        assert_eq!(config.fullscreen, FullscreenMode::Windowed);
+
+        let config = WindowConfig::from_ron_str("(fullscreen: Borderless(monitor: Some(1)))").unwrap();
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless { monitor: Some(1) });
+    }
+}
@@ -9,7 +9,15 @@ pub async fn create_graphics_context(
     window_config: Arc<Mutex<WindowConfig>>
 ) -> GraphicsContext {
     let size = window.inner_size();
-    let window_config = window_config.lock();
+    let (power_preference, desired_present_mode, force_fallback_adapter, transparent) = {
+        let window_config = window_config.lock();
+        (
+            window_config.power_preference,
+            window_config.vsync_mode,
+            window_config.force_fallback_adapter,
+            window_config.transparent,
+        )
+    };
 
     let instance = Instance::new(&InstanceDescriptor {
         backends: Backends::VULKAN,
@@ -23,36 +31,16 @@ pub async fn create_graphics_context(
 
     let adapter = instance
         .request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: window_config.power_preference,
+            power_preference,
             compatible_surface: Some(&surface),
-            force_fallback_adapter: false,
+            force_fallback_adapter,
         })
         .await
         .expect("adapter config must be valid");
 
     info!("Using adapter: {:?}", adapter.get_info().name);
 
-    trace!("Requesting device");
-
-    let limits = wgpu::Limits {
-        max_texture_dimension_2d: 4096,
-        ..wgpu::Limits::downlevel_defaults()
-    };
-
-    // TODO: adapter.features();
-
-    let (device, queue) = adapter.request_device(
-        &DeviceDescriptor {
-            label: None,
-            required_features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
-            required_limits: limits,
-            
-            ..Default::default()
-        },
-        None, // Optionally provide a trace path for debugging
-    )
-    .await
-    .expect("failed to create wgpu adapter");
+    let (device, queue) = request_device(&adapter).await;
 
     let caps = surface.get_capabilities(&adapter);
     let supported_formats = caps.formats;
@@ -78,23 +66,19 @@ pub async fn create_graphics_context(
     let surface_usage =
         wgpu::TextureUsages::RENDER_ATTACHMENT;
 
-    let desired_present_mode = window_config.vsync_mode;
-
-    let present_mode = if caps.present_modes.contains(&desired_present_mode) {
-        desired_present_mode
-    } else {
-        caps.present_modes[0]
-    };
+    let present_mode = choose_present_mode(&caps.present_modes, desired_present_mode);
 
     info!("ACTUAL PRESENT MODE: {:?}", present_mode);
 
+    let alpha_mode = choose_alpha_mode(&caps.alpha_modes, transparent);
+
     let config = wgpu::SurfaceConfiguration {
         usage: surface_usage,
         format: monitor_surface_format,
         width: size.width.max(1),
         height: size.height.max(1),
         present_mode,
-        alpha_mode: caps.alpha_modes[0],
+        alpha_mode,
         desired_maximum_frame_latency: 2,
         view_formats: vec![],
     };
@@ -103,36 +87,9 @@ pub async fn create_graphics_context(
 
     surface.configure(&device, &config);
 
-    let texture_bind_group_layout =
-        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        multisampled: false,
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        sample_type: wgpu::TextureSampleType::Float {
-                            filterable: true,
-                        },
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(
-                        wgpu::SamplerBindingType::Filtering,
-                    ),
-                    count: None,
-                },
-            ],
-            label: Some("texture_bind_group_layout"),
-        });
-
     let device = Arc::new(device);
     let queue = Arc::new(queue);
-    let texture_layout = Arc::new(texture_bind_group_layout);
+    let texture_layout = Arc::new(create_texture_bind_group_layout(&device));
 
     GraphicsContext {
         queue,
@@ -141,5 +98,287 @@ pub async fn create_graphics_context(
         surface: Some(Arc::new(surface)),
         instance: Arc::new(instance),
         config: Arc::new(Mutex::new(config)),
+        texture_layout,
+    }
+}
+
+/// 选择 surface 支持的呈现模式，不支持时使用第一个支持的模式
+///
+/// `AutoVsync` / `AutoNoVsync` 由 wgpu 在配置 surface 时自行选择，总是可用
+pub fn choose_present_mode(
+    supported_present_modes: &[PresentMode],
+    desired_present_mode: PresentMode,
+) -> PresentMode {
+    match desired_present_mode {
+        PresentMode::AutoVsync | PresentMode::AutoNoVsync => desired_present_mode,
+        _ if supported_present_modes.contains(&desired_present_mode) => desired_present_mode,
+        _ => {
+            warn!(
+                "Unsupported present mode: {:?}. Using {:?}",
+                desired_present_mode, supported_present_modes[0]
+            );
+
+            supported_present_modes[0]
+        }
+    }
+}
+
+/// 选择 surface 的 alpha 模式，透明窗口需要和桌面混合，其他情况使用不透明模式
+pub fn choose_alpha_mode(
+    supported_alpha_modes: &[wgpu::CompositeAlphaMode],
+    transparent: bool,
+) -> wgpu::CompositeAlphaMode {
+    use wgpu::CompositeAlphaMode;
+
+    let preferred: &[CompositeAlphaMode] = if transparent {
+        &[
+            CompositeAlphaMode::PreMultiplied,
+            CompositeAlphaMode::PostMultiplied,
+            CompositeAlphaMode::Inherit,
+        ]
+    } else {
+        &[CompositeAlphaMode::Opaque, CompositeAlphaMode::Inherit]
+    };
+
+    if let Some(mode) = preferred.iter().find(|mode| supported_alpha_modes.contains(mode)) {
+        return *mode;
+    }
+
+    let fallback = supported_alpha_modes
+        .first()
+        .copied()
+        .unwrap_or(CompositeAlphaMode::Auto);
+
+    if transparent {
+        warn!("Transparent windows are not supported by the surface. Using {:?}", fallback);
+    }
+
+    fallback
+}
+
+/// 是否有可以用于无窗口渲染的适配器，没有显卡和软件渲染的 CI 上可以用来跳过测试
+#[cfg(test)]
+pub(crate) fn headless_adapter_available() -> bool {
+    Instance::new(&InstanceDescriptor {
+        backends: Backends::all(),
+        ..Default::default()
+    })
+    .request_adapter(&wgpu::RequestAdapterOptions::default())
+    .block_on()
+    .is_some()
+}
+
+/// 不创建窗口和 surface 的图形上下文，优先使用软件渲染适配器，用于 CI 等没有显示器的环境
+pub async fn create_headless_graphics_context(
+    window_config: Arc<Mutex<WindowConfig>>,
+    size: PhysicalSize<u32>,
+) -> GraphicsContext {
+    let power_preference = window_config.lock().power_preference;
+
+    let instance = Instance::new(&InstanceDescriptor {
+        backends: Backends::all(),
+        ..Default::default()
+    });
+
+    trace!("Requesting headless adapter");
+
+    let software_adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference,
+            compatible_surface: None,
+            force_fallback_adapter: true,
+        })
+        .await;
+
+    let adapter = match software_adapter {
+        Some(adapter) => adapter,
+        None => {
+            warn!("No software adapter available, falling back to any adapter");
+
+            instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference,
+                    compatible_surface: None,
+                    force_fallback_adapter: false,
+                })
+                .await
+                .expect("no adapter available for headless rendering")
+        }
+    };
+
+    info!("Using headless adapter: {:?}", adapter.get_info().name);
+
+    let (device, queue) = request_device(&adapter).await;
+
+    // 没有 surface，这份配置只用来记录离屏目标的格式和尺寸
+    let config = wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        width: size.width.max(1),
+        height: size.height.max(1),
+        present_mode: PresentMode::AutoNoVsync,
+        alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+        desired_maximum_frame_latency: 2,
+        view_formats: vec![],
+    };
+
+    let device = Arc::new(device);
+    let queue = Arc::new(queue);
+    let texture_layout = Arc::new(create_texture_bind_group_layout(&device));
+
+    GraphicsContext {
+        queue,
+        device,
+        adapter: Arc::new(adapter),
+        surface: None,
+        instance: Arc::new(instance),
+        config: Arc::new(Mutex::new(config)),
+        texture_layout,
+    }
+}
+
+async fn request_device(adapter: &Adapter) -> (Device, Queue) {
+    trace!("Requesting device");
+
+    let limits = wgpu::Limits {
+        max_texture_dimension_2d: 4096,
+        ..wgpu::Limits::downlevel_defaults()
+    };
+
+    // 软件适配器不一定支持，只在可用时开启
+    let required_features = adapter.features()
+        & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+
+    adapter.request_device(
+        &DeviceDescriptor {
+            label: None,
+            required_features,
+            required_limits: limits,
+
+            ..Default::default()
+        },
+        None, // Optionally provide a trace path for debugging
+    )
+    .await
+    .expect("failed to create wgpu adapter")
+}
+
+fn create_texture_bind_group_layout(device: &Device) -> BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    sample_type: wgpu::TextureSampleType::Float {
+                        filterable: true,
+                    },
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(
+                    wgpu::SamplerBindingType::Filtering,
+                ),
+                count: None,
+            },
+        ],
+        label: Some("texture_bind_group_layout"),
+    })
+}
+
+/// 检查适配器是否支持请求的采样数，不支持时退回到不开启 MSAA
+pub fn supported_sample_count(
+    adapter: &Adapter,
+    format: wgpu::TextureFormat,
+    requested: u32,
+) -> u32 {
+    let flags = adapter.get_texture_format_features(format).flags;
+
+    if flags.sample_count_supported(requested) {
+        requested
+    } else {
+        warn!(
+            "MSAA x{requested} is not supported for {format:?}, supported: {:?}. Disabling MSAA",
+            flags.supported_sample_counts()
+        );
+
+        1
+    }
+}
+
+pub fn create_multisampled_framebuffer(
+    device: &Device,
+    config: &SurfaceConfiguration,
+    sample_count: u32,
+) -> Option<wgpu::TextureView> {
+    if sample_count <= 1 {
+        return None;
+    }
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("multisampled_framebuffer"),
+        size: wgpu::Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format: config.format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+
+    Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+}
+
+pub fn create_offscreen_texture(
+    device: &Device,
+    config: &SurfaceConfiguration,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("offscreen_target"),
+        size: wgpu::Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: config.format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wgpu::CompositeAlphaMode::*;
+
+    #[test]
+    fn transparent_prefers_premultiplied_alpha() {
+        assert_eq!(choose_alpha_mode(&[Opaque, PostMultiplied, PreMultiplied], true), PreMultiplied);
+        assert_eq!(choose_alpha_mode(&[Opaque, PostMultiplied], true), PostMultiplied);
+        assert_eq!(choose_alpha_mode(&[Opaque, Inherit], true), Inherit);
+
+        // 不支持透明时退回到第一个可用的模式
+        assert_eq!(choose_alpha_mode(&[Opaque], true), Opaque);
+        assert_eq!(choose_alpha_mode(&[], true), Auto);
+    }
+
+    #[test]
+    fn opaque_prefers_opaque_alpha() {
+        assert_eq!(choose_alpha_mode(&[PreMultiplied, Opaque], false), Opaque);
+        assert_eq!(choose_alpha_mode(&[PreMultiplied, Inherit], false), Inherit);
+        assert_eq!(choose_alpha_mode(&[PostMultiplied], false), PostMultiplied);
     }
 }
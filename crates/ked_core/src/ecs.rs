@@ -0,0 +1,639 @@
+use crate::*;
+
+use std::any::{Any, TypeId};
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
+
+/// 实体编号，实体被销毁后编号会被复用，但 `generation` 不同
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+type Storage<T> = RefCell<Vec<Option<T>>>;
+
+trait AnyStorage {
+    fn remove(&mut self, index: usize);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: 'static> AnyStorage for Storage<T> {
+    fn remove(&mut self, index: usize) {
+        if let Some(slot) = self.get_mut().get_mut(index) {
+            *slot = None;
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// 一组可以一起添加到实体上的组件，为最多 8 个元素的元组实现
+pub trait Bundle {
+    fn insert_into(self, world: &mut World, entity: Entity);
+}
+
+/// 可以在 `World::query` 中使用的查询，`&T`、`&mut T` 以及它们组成的元组
+///
+/// 同一个查询中对同一种组件同时使用 `&mut T` 和 `&T` 会 panic
+pub trait Query {
+    type Fetch<'w>;
+    type Item<'f>;
+
+    fn fetch(world: &World) -> Option<Self::Fetch<'_>>;
+
+    fn get<'f>(fetch: &'f mut Self::Fetch<'_>, index: usize) -> Option<Self::Item<'f>>;
+}
+
+impl<T: 'static> Query for &T {
+    type Fetch<'w> = Ref<'w, Vec<Option<T>>>;
+    type Item<'f> = &'f T;
+
+    fn fetch(world: &World) -> Option<Self::Fetch<'_>> {
+        Some(world.storage::<T>()?.borrow())
+    }
+
+    fn get<'f>(fetch: &'f mut Self::Fetch<'_>, index: usize) -> Option<Self::Item<'f>> {
+        fetch.get(index)?.as_ref()
+    }
+}
+
+impl<T: 'static> Query for &mut T {
+    type Fetch<'w> = RefMut<'w, Vec<Option<T>>>;
+    type Item<'f> = &'f mut T;
+
+    fn fetch(world: &World) -> Option<Self::Fetch<'_>> {
+        Some(world.storage::<T>()?.borrow_mut())
+    }
+
+    fn get<'f>(fetch: &'f mut Self::Fetch<'_>, index: usize) -> Option<Self::Item<'f>> {
+        fetch.get_mut(index)?.as_mut()
+    }
+}
+
+macro_rules! impl_tuples {
+    ($($name:ident),+) => {
+        impl<$($name: 'static),+> Bundle for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn insert_into(self, world: &mut World, entity: Entity) {
+                let ($($name,)+) = self;
+                $(world.insert(entity, $name);)+
+            }
+        }
+
+        impl<$($name: Query),+> Query for ($($name,)+) {
+            type Fetch<'w> = ($($name::Fetch<'w>,)+);
+            type Item<'f> = ($($name::Item<'f>,)+);
+
+            fn fetch(world: &World) -> Option<Self::Fetch<'_>> {
+                Some(($($name::fetch(world)?,)+))
+            }
+
+            #[allow(non_snake_case)]
+            fn get<'f>(fetch: &'f mut Self::Fetch<'_>, index: usize) -> Option<Self::Item<'f>> {
+                let ($($name,)+) = fetch;
+                Some(($($name::get($name, index)?,)+))
+            }
+        }
+    };
+}
+
+impl_tuples!(A);
+impl_tuples!(A, B);
+impl_tuples!(A, B, C);
+impl_tuples!(A, B, C, D);
+impl_tuples!(A, B, C, D, E);
+impl_tuples!(A, B, C, D, E, F);
+impl_tuples!(A, B, C, D, E, F, G);
+impl_tuples!(A, B, C, D, E, F, G, H);
+
+impl Bundle for () {
+    fn insert_into(self, _world: &mut World, _entity: Entity) {}
+}
+
+/// 轻量的实体组件容器，任何 `'static` 类型都可以作为组件
+///
+/// ```
+/// use ked_core::*;
+///
+/// struct Velocity(Vec2);
+///
+/// let mut world = World::new();
+/// let entity = world.spawn((Transform::from_position(vec2(10.0, 0.0)), Velocity(vec2(1.0, 2.0))));
+///
+/// world.query::<(&mut Transform, &Velocity)>(|_, (transform, velocity)| {
+///     transform.position += velocity.0;
+/// });
+///
+/// assert_eq!(world.get::<Transform>(entity).unwrap().position, vec2(11.0, 2.0));
+/// ```
+#[derive(Default)]
+pub struct World {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<u32>,
+    len: usize,
+
+    storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+
+    /// `Camera` 实体接管的主相机
+    camera: Option<WorldCamera>,
+}
+
+/// 由 `render_world` 创建的主相机，以及接管之前的主相机
+struct WorldCamera {
+    handle: Arc<Mutex<Camera2D>>,
+    previous: Option<Arc<Mutex<Camera2D>>>,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        let entity = match self.free.pop() {
+            Some(index) => {
+                self.alive[index as usize] = true;
+                Entity { index, generation: self.generations[index as usize] }
+            }
+            None => {
+                self.generations.push(0);
+                self.alive.push(true);
+                Entity { index: self.generations.len() as u32 - 1, generation: 0 }
+            }
+        };
+
+        self.len += 1;
+        bundle.insert_into(self, entity);
+
+        entity
+    }
+
+    /// 销毁实体和它的所有组件，实体不存在时返回 false
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.contains(entity) {
+            return false;
+        }
+
+        let index = entity.index as usize;
+        for storage in self.storages.values_mut() {
+            storage.remove(index);
+        }
+
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(entity.index);
+        self.len -= 1;
+
+        true
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+
+        self.alive.get(index).copied().unwrap_or(false)
+            && self.generations[index] == entity.generation
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 销毁所有实体，旧的 `Entity` 句柄不会指向之后创建的实体
+    ///
+    /// 相机接管的记录会保留，下一帧由 `render_world` 恢复之前的主相机
+    pub fn clear(&mut self) {
+        let entities: Vec<_> = self.entities().collect();
+
+        for entity in entities {
+            self.despawn(entity);
+        }
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.alive
+            .iter()
+            .enumerate()
+            .filter(|(_, alive)| **alive)
+            .map(|(index, _)| Entity {
+                index: index as u32,
+                generation: self.generations[index],
+            })
+    }
+
+    /// 添加或替换组件，返回旧的组件，实体不存在时什么也不做
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) -> Option<T> {
+        if !self.contains(entity) {
+            return None;
+        }
+
+        let index = entity.index as usize;
+        let storage = self
+            .storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Storage::<T>::default()))
+            .as_any_mut()
+            .downcast_mut::<Storage<T>>()
+            .expect("component storage type must match its TypeId")
+            .get_mut();
+
+        if storage.len() <= index {
+            storage.resize_with(index + 1, || None);
+        }
+
+        storage[index].replace(component)
+    }
+
+    pub fn remove<T: 'static>(&mut self, entity: Entity) -> Option<T> {
+        if !self.contains(entity) {
+            return None;
+        }
+
+        self.storages
+            .get_mut(&TypeId::of::<T>())?
+            .as_any_mut()
+            .downcast_mut::<Storage<T>>()?
+            .get_mut()
+            .get_mut(entity.index as usize)?
+            .take()
+    }
+
+    /// 实体不存在或没有该组件时返回 None，组件正被可变借用时 panic
+    pub fn get<T: 'static>(&self, entity: Entity) -> Option<Ref<'_, T>> {
+        if !self.contains(entity) {
+            return None;
+        }
+
+        let storage = self.storage::<T>()?.borrow();
+        Ref::filter_map(storage, |s| s.get(entity.index as usize)?.as_ref()).ok()
+    }
+
+    pub fn get_mut<T: 'static>(&self, entity: Entity) -> Option<RefMut<'_, T>> {
+        if !self.contains(entity) {
+            return None;
+        }
+
+        let storage = self.storage::<T>()?.borrow_mut();
+        RefMut::filter_map(storage, |s| s.get_mut(entity.index as usize)?.as_mut()).ok()
+    }
+
+    pub fn has<T: 'static>(&self, entity: Entity) -> bool {
+        self.get::<T>(entity).is_some()
+    }
+
+    /// 对拥有查询中所有组件的实体调用 `f`
+    pub fn query<Q: Query>(&self, mut f: impl FnMut(Entity, Q::Item<'_>)) {
+        let Some(mut fetch) = Q::fetch(self) else {
+            return;
+        };
+
+        for (index, alive) in self.alive.iter().enumerate() {
+            if !alive {
+                continue;
+            }
+
+            if let Some(item) = Q::get(&mut fetch, index) {
+                let entity = Entity {
+                    index: index as u32,
+                    generation: self.generations[index],
+                };
+
+                f(entity, item);
+            }
+        }
+    }
+
+    fn storage<T: 'static>(&self) -> Option<&Storage<T>> {
+        self.storages
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref()
+    }
+}
+
+/// 实体的位置、旋转和缩放，`position` 为实体中心的世界坐标
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub position: Vec2,
+    /// 旋转角度（弧度）
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            rotation: 0.0,
+            scale: Vec2::ONE,
+        }
+    }
+}
+
+impl Transform {
+    pub fn from_position(position: Vec2) -> Self {
+        Self {
+            position,
+            ..Default::default()
+        }
+    }
+}
+
+/// 和 `Transform` 一起使用时由引擎每帧自动绘制
+#[derive(Clone)]
+pub struct Sprite {
+    pub texture: Texture2D,
+    pub color: Color,
+    /// 纹理中要绘制的部分（像素），默认为整张纹理
+    pub source: Option<Rect>,
+    /// 缩放前的尺寸，默认为源区域的尺寸
+    pub size: Option<Vec2>,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// 越大越靠上
+    pub z_index: i32,
+}
+
+impl Sprite {
+    pub fn new(texture: Texture2D) -> Self {
+        Self {
+            texture,
+            color: WHITE,
+            source: None,
+            size: None,
+            flip_x: false,
+            flip_y: false,
+            z_index: 0,
+        }
+    }
+}
+
+/// 和 `Transform` 一起使用时，第一个 `active` 的相机会被设置为主相机
+///
+/// 之前的主相机会被保存，没有 `active` 的相机之后恢复
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub zoom: f32,
+    /// 相机绘制到屏幕上的区域（像素），`None` 为整个窗口
+    pub viewport: Option<Rect>,
+    pub active: bool,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            viewport: None,
+            active: true,
+        }
+    }
+}
+
+/// 用 `Camera` 实体更新主相机，只修改由 World 创建的相机
+fn sync_main_camera(
+    owned: &mut Option<WorldCamera>,
+    renderer: &mut WgpuRenderer,
+    camera: Option<(Transform, Camera)>,
+) {
+    let current = renderer.main_camera();
+    let is_owned = |owned: &WorldCamera| current.as_ref().is_some_and(|c| Arc::ptr_eq(c, &owned.handle));
+
+    let Some((transform, camera)) = camera else {
+        // 相机实体没有了，恢复之前的主相机；游戏在此期间设置的相机保持不变
+        if let Some(owned) = owned.take().filter(is_owned) {
+            renderer.set_main_camera_handle(owned.previous);
+        }
+
+        return;
+    };
+
+    match owned.as_ref().filter(|owned| is_owned(owned)) {
+        Some(owned) => {
+            let mut handle = owned.handle.lock();
+            handle.position = transform.position;
+            handle.zoom = camera.zoom;
+            handle.rotation = transform.rotation;
+            handle.viewport = camera.viewport;
+        }
+        None => {
+            let mut camera2d = Camera2D::new(transform.position, camera.zoom);
+            camera2d.rotation = transform.rotation;
+            camera2d.viewport = camera.viewport;
+
+            *owned = Some(WorldCamera {
+                handle: renderer.set_main_camera(camera2d),
+                previous: current,
+            });
+        }
+    }
+}
+
+/// 由 `App` 在每帧 `GameLoop::render` 之前调用，绘制 World 中的精灵
+pub(crate) fn render_world(c: &mut EngineContext) {
+    let renderer = &mut *c.renderer;
+
+    let mut main_camera = None;
+    c.world.query::<(&Transform, &Camera)>(|_, (transform, camera)| {
+        if camera.active && main_camera.is_none() {
+            main_camera = Some((*transform, *camera));
+        }
+    });
+
+    sync_main_camera(&mut c.world.camera, renderer, main_camera);
+    let world = &*c.world;
+
+    let mut sprites = Vec::new();
+    world.query::<(&Transform, &Sprite)>(|entity, (_, sprite)| {
+        sprites.push((sprite.z_index, entity));
+    });
+
+    // 稳定排序，相同 z_index 时按实体顺序绘制
+    sprites.sort_by_key(|(z_index, _)| *z_index);
+
+    for (_, entity) in sprites {
+        let (Some(transform), Some(sprite)) = (world.get::<Transform>(entity), world.get::<Sprite>(entity))
+        else {
+            continue;
+        };
+
+        let source_size = sprite
+            .source
+            .map_or(sprite.texture.size(), |source| vec2(source.w, source.h));
+        let size = sprite.size.unwrap_or(source_size) * transform.scale;
+        let top_left = transform.position - size / 2.0;
+
+        renderer.draw_texture_ex(
+            &sprite.texture,
+            top_left.x,
+            top_left.y,
+            sprite.color,
+            DrawTextureParams {
+                dest_size: Some(size),
+                source: sprite.source,
+                rotation: transform.rotation,
+                flip_x: sprite.flip_x,
+                flip_y: sprite.flip_y,
+                pivot: None,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Health(u32);
+
+    #[test]
+    fn spawn_get_and_remove_components() {
+        let mut world = World::new();
+
+        let a = world.spawn((Health(10), Transform::default()));
+        let b = world.spawn((Health(20),));
+
+        assert_eq!(world.len(), 2);
+        assert_eq!(*world.get::<Health>(a).unwrap(), Health(10));
+        assert!(world.has::<Transform>(a));
+        assert!(!world.has::<Transform>(b));
+
+        world.get_mut::<Health>(b).unwrap().0 -= 5;
+        assert_eq!(world.remove::<Health>(b), Some(Health(15)));
+        assert!(world.get::<Health>(b).is_none());
+    }
+
+    #[test]
+    fn despawned_entities_are_not_reused_by_stale_handles() {
+        let mut world = World::new();
+
+        let old = world.spawn((Health(1),));
+        assert!(world.despawn(old));
+        assert!(!world.despawn(old));
+
+        let new = world.spawn((Health(2),));
+        assert_eq!(new.index(), old.index());
+        assert!(!world.contains(old));
+        assert!(world.get::<Health>(old).is_none());
+        assert_eq!(*world.get::<Health>(new).unwrap(), Health(2));
+    }
+
+    #[test]
+    fn clear_keeps_old_handles_stale() {
+        let mut world = World::new();
+
+        let old = world.spawn((Health(1),));
+        world.spawn((Health(2),));
+        world.clear();
+
+        assert!(world.is_empty());
+        assert!(!world.contains(old));
+
+        let new = world.spawn((Health(3),));
+        assert_ne!(new, old);
+        assert!(world.get::<Health>(old).is_none());
+        assert_eq!(*world.get::<Health>(new).unwrap(), Health(3));
+    }
+
+    #[test]
+    fn query_matches_entities_with_all_components() {
+        let mut world = World::new();
+
+        world.spawn((Health(1), Transform::default()));
+        world.spawn((Health(2),));
+        world.spawn((Health(3), Transform::default()));
+
+        let mut total = 0;
+        world.query::<(&mut Health, &Transform)>(|_, (health, _)| {
+            health.0 *= 10;
+            total += health.0;
+        });
+        assert_eq!(total, 40);
+
+        let mut count = 0;
+        world.query::<&Health>(|_, _| count += 1);
+        assert_eq!(count, 3);
+    }
+
+    /// 第 1 帧检查相机实体接管了主相机，第 2 帧检查是原地更新的，第 3 帧检查恢复了游戏的相机
+    struct CameraGame {
+        /// 第 2 帧用 `World::clear` 代替 `despawn`
+        clear: bool,
+        game_camera: Option<Arc<Mutex<Camera2D>>>,
+        world_camera: Option<Arc<Mutex<Camera2D>>>,
+        entity: Option<Entity>,
+        frame: u32,
+    }
+
+    impl GameLoop for CameraGame {
+        fn start(&mut self, c: &mut EngineContext) {
+            self.game_camera = Some(c.set_camera(Camera2D::new(vec2(1.0, 2.0), 1.0)));
+            self.entity = Some(c.world.spawn((Transform::from_position(vec2(50.0, 60.0)), Camera::default())));
+        }
+
+        fn update(&mut self, c: &mut EngineContext) {
+            let main_camera = c.renderer.main_camera().unwrap();
+            let entity = self.entity.unwrap();
+
+            match self.frame {
+                1 => {
+                    assert_eq!(main_camera.lock().position, vec2(50.0, 60.0));
+                    c.world.get_mut::<Transform>(entity).unwrap().position = vec2(70.0, 80.0);
+                    self.world_camera = Some(main_camera);
+                }
+                2 => {
+                    assert!(Arc::ptr_eq(&main_camera, self.world_camera.as_ref().unwrap()));
+                    assert_eq!(main_camera.lock().position, vec2(70.0, 80.0));
+                    if self.clear {
+                        c.world.clear();
+                    } else {
+                        c.world.despawn(entity);
+                    }
+                }
+                3 => {
+                    assert!(Arc::ptr_eq(&main_camera, self.game_camera.as_ref().unwrap()));
+                    assert_eq!(main_camera.lock().position, vec2(1.0, 2.0));
+                }
+                _ => (),
+            }
+
+            self.frame += 1;
+        }
+    }
+
+    #[test]
+    fn camera_entity_takes_over_and_restores_main_camera() {
+        if !headless_adapter_available() {
+            eprintln!("skipping: no adapter available for headless rendering");
+            return;
+        }
+
+        for clear in [false, true] {
+            let game = CameraGame { clear, game_camera: None, world_camera: None, entity: None, frame: 0 };
+            let window_config = WindowConfig {
+                resolution: ResolutionConfig::Physical(8, 8),
+                ..Default::default()
+            };
+
+            App::new(game, window_config).run_headless_fixed(4, Duration::from_millis(16));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::*;
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// 窗口大小改变（物理像素）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowResized {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowFocusChanged {
+    pub focused: bool,
+}
+
+/// 窗口移动，坐标为窗口左上角在桌面上的物理像素位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowMoved {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleFactorChanged {
+    pub scale_factor: f64,
+}
+
+/// 用户点击了关闭按钮，是否退出由 `GameLoop::on_close_requested` 决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowCloseRequested;
+
+/// 拖放文件到窗口上
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDragAndDrop {
+    Hovered(PathBuf),
+    Dropped(PathBuf),
+    HoverCancelled,
+}
+
+trait EventQueue {
+    fn swap(&mut self);
+    fn clear(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+struct EventBuffer<T> {
+    /// 上一帧发送的事件，本帧可以读取
+    readable: Vec<T>,
+    /// 本帧发送的事件，下一帧才能读取
+    pending: Vec<T>,
+}
+
+impl<T: 'static> EventQueue for EventBuffer<T> {
+    fn swap(&mut self) {
+        self.readable.clear();
+        std::mem::swap(&mut self.readable, &mut self.pending);
+    }
+
+    fn clear(&mut self) {
+        self.readable.clear();
+        self.pending.clear();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// 按类型区分的双缓冲事件队列
+///
+/// 一帧内发送的事件在下一帧对所有读者可见，之后被丢弃，
+/// 读取的顺序不会影响结果。引擎会在每帧开始前发送 `WindowResized`、
+/// `WindowFocusChanged`、`FileDragAndDrop` 等窗口事件
+///
+/// ```no_run
+/// use ked_core::*;
+///
+/// struct PlayerDied {
+///     score: u32,
+/// }
+///
+/// fn update(c: &mut EngineContext) {
+///     for dropped in c.events.read::<FileDragAndDrop>() {
+///         if let FileDragAndDrop::Dropped(path) = dropped {
+///             info!("dropped {}", path.display());
+///         }
+///     }
+///
+///     for event in c.events.read::<PlayerDied>() {
+///         info!("game over, score: {}", event.score);
+///     }
+///
+///     c.events.send(PlayerDied { score: 100 });
+/// }
+/// ```
+#[derive(Default)]
+pub struct Events {
+    queues: HashMap<TypeId, Box<dyn EventQueue>>,
+}
+
+impl Events {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 发送事件，下一帧可以读取
+    pub fn send<T: 'static>(&mut self, event: T) {
+        self.queues
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(EventBuffer::<T> {
+                readable: Vec::new(),
+                pending: Vec::new(),
+            }))
+            .as_any_mut()
+            .downcast_mut::<EventBuffer<T>>()
+            .expect("event queue type must match its TypeId")
+            .pending
+            .push(event);
+    }
+
+    /// 读取上一帧发送的所有 `T` 类型事件
+    pub fn read<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.queue::<T>()
+            .map_or(&[][..], |queue| queue.readable.as_slice())
+            .iter()
+    }
+
+    pub fn is_empty<T: 'static>(&self) -> bool {
+        self.queue::<T>().is_none_or(|queue| queue.readable.is_empty())
+    }
+
+    /// 清空所有事件，包括还没有到可读取时机的
+    pub fn clear(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.clear();
+        }
+    }
+
+    fn queue<T: 'static>(&self) -> Option<&EventBuffer<T>> {
+        self.queues
+            .get(&TypeId::of::<T>())
+            .and_then(|queue| queue.as_any().downcast_ref())
+    }
+
+    /// 每帧开始时交换缓冲
+    pub(crate) fn update(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.swap();
+        }
+    }
+
+    /// 把引擎关心的窗口事件转换成对应的事件类型
+    pub(crate) fn send_window_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::Resized(size) => self.send(WindowResized {
+                width: size.width,
+                height: size.height,
+            }),
+            WindowEvent::Moved(position) => self.send(WindowMoved {
+                x: position.x,
+                y: position.y,
+            }),
+            WindowEvent::Focused(focused) => self.send(WindowFocusChanged { focused: *focused }),
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => self.send(ScaleFactorChanged {
+                scale_factor: *scale_factor,
+            }),
+            WindowEvent::CloseRequested => self.send(WindowCloseRequested),
+            WindowEvent::HoveredFile(path) => self.send(FileDragAndDrop::Hovered(path.clone())),
+            WindowEvent::DroppedFile(path) => self.send(FileDragAndDrop::Dropped(path.clone())),
+            WindowEvent::HoveredFileCancelled => self.send(FileDragAndDrop::HoverCancelled),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Hit(u32);
+
+    #[test]
+    fn events_are_readable_for_one_frame() {
+        let mut events = Events::new();
+
+        events.send(Hit(1));
+        events.send(Hit(2));
+        assert!(events.is_empty::<Hit>());
+
+        events.update();
+        assert_eq!(events.read::<Hit>().collect::<Vec<_>>(), [&Hit(1), &Hit(2)]);
+
+        // 本帧发送的事件要到下一帧才能读到
+        events.send(Hit(3));
+        assert_eq!(events.read::<Hit>().count(), 2);
+
+        events.update();
+        assert_eq!(events.read::<Hit>().collect::<Vec<_>>(), [&Hit(3)]);
+
+        events.update();
+        assert!(events.is_empty::<Hit>());
+    }
+
+    #[test]
+    fn event_types_are_separate() {
+        let mut events = Events::new();
+
+        events.send(Hit(1));
+        events.send(WindowFocusChanged { focused: false });
+        events.update();
+
+        assert_eq!(events.read::<Hit>().count(), 1);
+        assert_eq!(
+            events.read::<WindowFocusChanged>().collect::<Vec<_>>(),
+            [&WindowFocusChanged { focused: false }]
+        );
+        assert_eq!(events.read::<WindowResized>().count(), 0);
+    }
+}
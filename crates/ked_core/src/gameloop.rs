@@ -7,4 +7,32 @@ use crate::*;
 pub trait GameLoop {
     fn start(&mut self, _c: &mut EngineContext);
     fn update(&mut self, _c: &mut EngineContext);
+
+    /// 以固定步长调用，在 update 之前执行，适合放物理等需要确定性的逻辑
+    fn fixed_update(&mut self, _c: &mut EngineContext) {}
+
+    /// 在所有 update 之后执行，适合让相机跟随等依赖本帧结果的逻辑
+    fn late_update(&mut self, _c: &mut EngineContext) {}
+
+    /// 在 late_update 之后、提交绘制之前执行，适合放纯绘制代码
+    fn render(&mut self, _c: &mut EngineContext) {}
+
+    /// 窗口大小改变，`size` 为新的物理像素尺寸
+    fn on_resize(&mut self, _c: &mut EngineContext, _size: PhysicalSize<u32>) {}
+
+    fn on_focus_changed(&mut self, _c: &mut EngineContext, _focused: bool) {}
+
+    /// 应用被挂起（Android 切到后台），此时 surface 已经释放
+    fn on_suspend(&mut self, _c: &mut EngineContext) {}
+
+    /// 应用从挂起状态恢复，第一次启动时调用的是 start
+    fn on_resume(&mut self, _c: &mut EngineContext) {}
+
+    /// 用户请求关闭窗口，返回 false 可以阻止退出
+    fn on_close_requested(&mut self, _c: &mut EngineContext) -> bool {
+        true
+    }
+
+    /// 事件循环退出前调用
+    fn on_exit(&mut self, _c: &mut EngineContext) {}
 }
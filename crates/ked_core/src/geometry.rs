@@ -0,0 +1,342 @@
+use crate::*;
+
+use std::path::PathBuf;
+
+use winit::monitor::MonitorHandle;
+
+/// 保存窗口状态的文件名，位于 `WindowConfig::user_settings_dir` 中
+pub const WINDOW_STATE_FILE_NAME: &str = "window_state.toml";
+
+/// 标题栏中点至少要落在某个显示器上，否则认为窗口位置已经失效
+const TITLE_BAR_HEIGHT: i32 = 16;
+
+/// 保存窗口位置时所在的显示器
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedMonitor {
+    pub name: Option<String>,
+    /// 显示器左上角在桌面上的位置，用来区分同名的显示器
+    pub x: i32,
+    pub y: i32,
+}
+
+impl SavedMonitor {
+    fn new(monitor: &MonitorHandle) -> Self {
+        let position = monitor.position();
+
+        Self {
+            name: monitor.name(),
+            x: position.x,
+            y: position.y,
+        }
+    }
+
+    /// 在当前的显示器中查找，优先名字和位置都相同的，其次名字相同的
+    fn find(&self, monitors: &[MonitorInfo]) -> Option<usize> {
+        let same_name = |m: &&MonitorInfo| m.name.is_some() && m.name == self.name;
+
+        monitors
+            .iter()
+            .filter(same_name)
+            .find(|m| m.position == PhysicalPosition::new(self.x, self.y))
+            .or_else(|| monitors.iter().find(same_name))
+            .map(|m| m.index)
+    }
+}
+
+/// 上次退出时的窗口大小、位置、显示器和全屏状态
+///
+/// `WindowConfig::remember_window` 开启时在退出时保存，下次创建窗口时恢复，
+/// 恢复的设置会覆盖配置文件中的对应项，命令行参数在恢复之后再次应用，仍然优先
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowGeometry {
+    /// 窗口模式下的内容区域大小（物理像素），不记录全屏和最大化时的大小
+    pub size: Option<(u32, u32)>,
+    /// 窗口左上角相对 `monitor` 左上角的位置（物理像素）
+    pub position: Option<(i32, i32)>,
+    pub monitor: Option<SavedMonitor>,
+    pub maximized: bool,
+    pub fullscreen: FullscreenMode,
+    /// 全屏时所在的显示器
+    pub fullscreen_monitor: Option<SavedMonitor>,
+}
+
+impl WindowGeometry {
+    pub fn path() -> Option<PathBuf> {
+        Some(WindowConfig::user_settings_dir()?.join(WINDOW_STATE_FILE_NAME))
+    }
+
+    /// 读取上次保存的窗口状态，文件不存在或无法解析时返回 None
+    pub fn load() -> Option<Self> {
+        let path = Self::path()?;
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                warn!("Failed to read window state from {}: {err}", path.display());
+                return None;
+            }
+        };
+
+        match toml::from_str(&source) {
+            Ok(geometry) => Some(geometry),
+            Err(err) => {
+                warn!("Failed to parse window state from {}: {err}", path.display());
+                None
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), ConfigError> {
+        let path = Self::path().ok_or_else(|| {
+            ConfigError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "user settings directory is unknown",
+            ))
+        })?;
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    /// 记录窗口模式下的大小和位置，全屏、最大化和最小化时不记录
+    pub(crate) fn track(&mut self, window: &Window) {
+        if window.fullscreen().is_some() || window.is_maximized() {
+            return;
+        }
+
+        // Windows 上最小化的窗口大小为 0，位置在 (-32000, -32000)，保留上一次正常的状态
+        let size = window.inner_size();
+        if window.is_minimized() == Some(true) || size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        self.size = Some((size.width, size.height));
+
+        // Wayland 等平台无法获取窗口位置
+        if let (Ok(position), Some(monitor)) = (window.outer_position(), window.current_monitor()) {
+            let origin = monitor.position();
+
+            self.position = Some((position.x - origin.x, position.y - origin.y));
+            self.monitor = Some(SavedMonitor::new(&monitor));
+        }
+    }
+
+    /// 退出前记录最大化和全屏状态
+    pub(crate) fn capture_exit(&mut self, window: &Window, fullscreen: FullscreenMode) {
+        self.track(window);
+        self.maximized = window.is_maximized();
+
+        if window.fullscreen().is_some() && fullscreen.is_fullscreen() {
+            self.fullscreen = fullscreen.with_monitor(None);
+            self.fullscreen_monitor = window.current_monitor().map(|monitor| SavedMonitor::new(&monitor));
+        } else {
+            self.fullscreen = FullscreenMode::Windowed;
+            self.fullscreen_monitor = None;
+        }
+    }
+
+    /// 把保存的状态应用到 `config`，显示器布局变化时把窗口移回可见的位置
+    pub(crate) fn restore(&self, config: &mut WindowConfig, monitors: &[MonitorInfo]) {
+        let saved_monitor = self.monitor.as_ref().and_then(|m| m.find(monitors));
+        let target = saved_monitor
+            .or_else(|| monitors.iter().find(|m| m.is_primary).map(|m| m.index))
+            .or_else(|| monitors.first().map(|m| m.index))
+            .and_then(|index| monitors.get(index));
+
+        // 旧版本在最小化时退出会保存 0x0 的大小，忽略它
+        let saved_size = self.size.filter(|&(width, height)| width > 0 && height > 0);
+
+        if let Some((mut width, mut height)) = saved_size {
+            // 显示器换成了更小的，窗口不能比显示器还大
+            if let Some(monitor) = target {
+                width = width.min(monitor.size.width);
+                height = height.min(monitor.size.height);
+            }
+
+            config.resolution = ResolutionConfig::Physical(width.max(1), height.max(1));
+        }
+
+        if let (Some(monitor), Some((width, height))) = (target, saved_size) {
+            let position = match (saved_monitor, self.position) {
+                (Some(_), Some((x, y))) => {
+                    let position = PhysicalPosition::new(monitor.position.x + x, monitor.position.y + y);
+                    let width = config.resolution.width();
+
+                    is_visible(monitors, position, width).then_some(position)
+                }
+                _ => None,
+            };
+
+            let position = position.unwrap_or_else(|| {
+                let (width, height) = (width.min(monitor.size.width), height.min(monitor.size.height));
+
+                PhysicalPosition::new(
+                    monitor.position.x + (monitor.size.width - width) as i32 / 2,
+                    monitor.position.y + (monitor.size.height - height) as i32 / 2,
+                )
+            });
+
+            config.position = WindowPosition::Physical(position.x, position.y);
+        }
+
+        config.maximized = self.maximized;
+
+        let fullscreen_monitor = self.fullscreen_monitor.as_ref().and_then(|m| m.find(monitors));
+        config.fullscreen = self.fullscreen.with_monitor(fullscreen_monitor);
+    }
+}
+
+/// 标题栏的中点是否在某个显示器上
+fn is_visible(monitors: &[MonitorInfo], position: PhysicalPosition<i32>, width: u32) -> bool {
+    let x = position.x + width as i32 / 2;
+    let y = position.y + TITLE_BAR_HEIGHT;
+
+    monitors.iter().any(|m| {
+        x >= m.position.x
+            && x < m.position.x + m.size.width as i32
+            && y >= m.position.y
+            && y < m.position.y + m.size.height as i32
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(index: usize, name: &str, x: i32, width: u32, is_primary: bool) -> MonitorInfo {
+        MonitorInfo {
+            index,
+            name: Some(name.to_owned()),
+            size: PhysicalSize::new(width, 1080),
+            position: PhysicalPosition::new(x, 0),
+            scale_factor: 1.0,
+            refresh_rate_millihertz: Some(60000),
+            is_primary,
+            video_modes: Vec::new(),
+        }
+    }
+
+    fn saved(name: &str, x: i32) -> Option<SavedMonitor> {
+        Some(SavedMonitor {
+            name: Some(name.to_owned()),
+            x,
+            y: 0,
+        })
+    }
+
+    #[test]
+    fn restore_on_same_monitor() {
+        let monitors = [monitor(0, "A", 0, 1920, true), monitor(1, "B", 1920, 2560, false)];
+        let geometry = WindowGeometry {
+            size: Some((1280, 720)),
+            position: Some((100, 50)),
+            monitor: saved("B", 1920),
+            fullscreen: FullscreenMode::Borderless { monitor: None },
+            fullscreen_monitor: saved("B", 1920),
+            ..Default::default()
+        };
+
+        let mut config = WindowConfig::default();
+        geometry.restore(&mut config, &monitors);
+
+        assert_eq!(config.resolution, ResolutionConfig::Physical(1280, 720));
+        assert_eq!(config.position, WindowPosition::Physical(2020, 50));
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless { monitor: Some(1) });
+    }
+
+    #[test]
+    fn monitor_moved_keeps_relative_position() {
+        // B 从右边移到了左边
+        let monitors = [monitor(0, "A", 0, 1920, true), monitor(1, "B", -2560, 2560, false)];
+        let geometry = WindowGeometry {
+            size: Some((1280, 720)),
+            position: Some((100, 50)),
+            monitor: saved("B", 1920),
+            ..Default::default()
+        };
+
+        let mut config = WindowConfig::default();
+        geometry.restore(&mut config, &monitors);
+
+        assert_eq!(config.position, WindowPosition::Physical(-2460, 50));
+        assert_eq!(config.fullscreen, FullscreenMode::Windowed);
+    }
+
+    #[test]
+    fn missing_monitor_centers_on_primary() {
+        let monitors = [monitor(0, "A", 0, 1920, true)];
+        let geometry = WindowGeometry {
+            size: Some((2560, 1440)),
+            position: Some((100, 50)),
+            monitor: saved("B", 1920),
+            fullscreen: FullscreenMode::Borderless { monitor: None },
+            fullscreen_monitor: saved("B", 1920),
+            ..Default::default()
+        };
+
+        let mut config = WindowConfig::default();
+        geometry.restore(&mut config, &monitors);
+
+        // 窗口缩小到显示器大小并居中
+        assert_eq!(config.resolution, ResolutionConfig::Physical(1920, 1080));
+        assert_eq!(config.position, WindowPosition::Physical(0, 0));
+        assert_eq!(config.fullscreen, FullscreenMode::Borderless { monitor: None });
+    }
+
+    #[test]
+    fn offscreen_position_is_reset() {
+        let monitors = [monitor(0, "A", 0, 1920, true)];
+        let geometry = WindowGeometry {
+            size: Some((800, 600)),
+            position: Some((5000, 50)),
+            monitor: saved("A", 0),
+            ..Default::default()
+        };
+
+        let mut config = WindowConfig::default();
+        geometry.restore(&mut config, &monitors);
+
+        assert_eq!(config.position, WindowPosition::Physical(560, 240));
+    }
+
+    #[test]
+    fn cli_args_applied_after_restore() {
+        let monitors = [monitor(0, "A", 0, 1920, true)];
+        let geometry = WindowGeometry {
+            size: Some((1280, 720)),
+            fullscreen: FullscreenMode::Borderless { monitor: None },
+            fullscreen_monitor: saved("A", 0),
+            ..Default::default()
+        };
+
+        let mut config = WindowConfig::default();
+        geometry.restore(&mut config, &monitors);
+        config.apply_args(&["--windowed", "--resolution", "800x600"]).unwrap();
+
+        assert_eq!(config.resolution, ResolutionConfig::Physical(800, 600));
+        assert_eq!(config.fullscreen, FullscreenMode::Windowed);
+    }
+
+    #[test]
+    fn zero_size_is_ignored() {
+        let monitors = [monitor(0, "A", 0, 1920, true)];
+        let geometry = WindowGeometry {
+            size: Some((0, 0)),
+            position: Some((-32000, -32000)),
+            monitor: saved("A", 0),
+            ..Default::default()
+        };
+
+        let mut config = WindowConfig::default();
+        geometry.restore(&mut config, &monitors);
+
+        assert_eq!(config.resolution, WindowConfig::default().resolution);
+        assert_eq!(config.position, WindowConfig::default().position);
+    }
+}
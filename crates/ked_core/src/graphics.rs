@@ -6,26 +6,132 @@ pub struct GraphicsContext {
     pub adapter: Arc<Adapter>,
     pub device: Arc<Device>,
     pub queue: Arc<Queue>,
-    pub config: Arc<Mutex<SurfaceConfiguration>>
+    pub config: Arc<Mutex<SurfaceConfiguration>>,
+    pub texture_layout: Arc<BindGroupLayout>,
 }
 
 pub struct WgpuRenderer {
     pub context: GraphicsContext,
     pub size: PhysicalSize<u32>,
+
+    window_config: Arc<Mutex<WindowConfig>>,
+
+    /// 无窗口模式下的渲染目标
+    offscreen_texture: Option<wgpu::Texture>,
+
+    /// 最近一次应用的 `WindowConfig::sample_count`，实际的采样数可能因为不支持而更低
+    requested_msaa: Msaa,
+    sample_count: u32,
+    msaa_texture: Option<wgpu::TextureView>,
+
+    /// 最近一次应用的 `WindowConfig::vsync_mode`
+    present_mode: PresentMode,
+
+    main_camera: Option<Arc<Mutex<Camera2D>>>,
+
+    camera_layout: BindGroupLayout,
+    sprite_pipeline: wgpu::RenderPipeline,
+    camera_buffer: Buffer,
+    camera_bind_group: BindGroup,
+
+    batch: SpriteBatch,
+    white_texture: Texture2D,
 }
 
 impl WgpuRenderer {
     pub async fn new(
         window: Arc<Window>,
         window_config: Arc<Mutex<WindowConfig>>
-    ) -> Self { 
+    ) -> Self {
         let size = window.inner_size();
-        let context = create_graphics_context(window, window_config).await;
-        
+        let context = create_graphics_context(window, Arc::clone(&window_config)).await;
+
+        Self::from_context(context, size, window_config)
+    }
+
+    /// 不创建窗口，渲染到一张离屏纹理上，之后可以通过 `read_pixels` 读回
+    pub async fn new_headless(
+        window_config: Arc<Mutex<WindowConfig>>,
+        size: PhysicalSize<u32>,
+    ) -> Self {
+        let context = create_headless_graphics_context(Arc::clone(&window_config), size).await;
+
+        let mut renderer = Self::from_context(context, size, window_config);
+        renderer.offscreen_texture = Some(create_offscreen_texture(
+            &renderer.context.device,
+            &renderer.context.config.lock(),
+        ));
+
+        renderer
+    }
+
+    fn from_context(
+        context: GraphicsContext,
+        size: PhysicalSize<u32>,
+        window_config: Arc<Mutex<WindowConfig>>,
+    ) -> Self {
+        let device = &context.device;
+        let config = context.config.lock().clone();
+
+        let requested_msaa = window_config.lock().sample_count.clone();
+        let sample_count = supported_sample_count(
+            &context.adapter,
+            config.format,
+            requested_msaa.clone().into(),
+        );
+        let msaa_texture = create_multisampled_framebuffer(device, &config, sample_count);
+        let present_mode = window_config.lock().vsync_mode;
+
+        let camera_layout = create_camera_bind_group_layout(device);
+        let sprite_pipeline = create_sprite_pipeline(
+            device,
+            config.format,
+            sample_count,
+            &camera_layout,
+            &context.texture_layout,
+        );
+
+        let camera_buffer = device.create_buffer_init(&util::BufferInitDescriptor {
+            label: Some("camera_buffer"),
+            contents: bytemuck::cast_slice(&[CameraUniform::new(Mat4::IDENTITY)]),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("camera_bind_group"),
+            layout: &camera_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let batch = SpriteBatch::new(device);
+        let white_texture = Texture2D::white(&context);
+
         Self {
             context,
+            size,
+
+            window_config,
+
+            offscreen_texture: None,
+
+            requested_msaa,
+            sample_count,
+            msaa_texture,
+
+            present_mode,
+
+            main_camera: None,
+
+            camera_layout,
+            sprite_pipeline,
+            camera_buffer,
+            camera_bind_group,
 
-            size
+            batch,
+            white_texture,
         }
     }
 
@@ -35,30 +141,371 @@ impl WgpuRenderer {
             new_size.height = new_size.height.max(1);
             self.size = new_size;
 
-            /* if let Some(main_camera) = &self.main_camera {
+            if let Some(main_camera) = &self.main_camera {
                 main_camera.lock().resize(new_size);
-            } */
+            }
 
-            if let Some(surface) = &self.context.surface.as_mut() {
-                let mut config = 
+            {
+                let mut config =
                 self.context.config.lock();
 
                 config.width = new_size.width;
                 config.height = new_size.height;
 
-                surface.configure(&self.context.device, &config);
+                if let Some(surface) = &self.context.surface.as_mut() {
+                    surface.configure(&self.context.device, &config);
+                }
             }
 
             self.update_resources();
         }
     }
 
+    /// 挂起恢复后重新创建 surface，窗口尺寸可能已经变化
+    pub fn resume(&mut self, window: Arc<Window>) {
+        self.context.resume(window);
+
+        let config = self.context.config.lock().clone();
+        self.size = PhysicalSize::new(config.width, config.height);
+
+        self.update_resources();
+    }
+
     fn update_resources(&mut self) {
-        /* self.msaa_texture = create_multisampled_framebuffer(
-            &self.context.device, 
-            &self.context.config.borrow(), 
-            window_config().sample_count.clone().into()
-        ); */
+        if self.offscreen_texture.is_some() {
+            self.offscreen_texture = Some(create_offscreen_texture(
+                &self.context.device,
+                &self.context.config.lock(),
+            ));
+        }
+
+        self.msaa_texture = create_multisampled_framebuffer(
+            &self.context.device,
+            &self.context.config.lock(),
+            self.sample_count,
+        );
+    }
+
+    /// WindowConfig 中的 MSAA 设置改变后，重建管线和多重采样缓冲
+    fn sync_sample_count(&mut self) {
+        let requested_msaa = self.window_config.lock().sample_count.clone();
+        if requested_msaa == self.requested_msaa {
+            return;
+        }
+
+        let format = self.context.config.lock().format;
+        let sample_count = supported_sample_count(
+            &self.context.adapter,
+            format,
+            requested_msaa.clone().into(),
+        );
+
+        self.requested_msaa = requested_msaa;
+
+        if sample_count == self.sample_count {
+            return;
+        }
+
+        self.sample_count = sample_count;
+        self.sprite_pipeline = create_sprite_pipeline(
+            &self.context.device,
+            format,
+            sample_count,
+            &self.camera_layout,
+            &self.context.texture_layout,
+        );
+
+        self.update_resources();
+    }
+
+    /// WindowConfig 中的 vsync_mode 改变后，重新配置 surface
+    fn sync_present_mode(&mut self) {
+        let desired_present_mode = self.window_config.lock().vsync_mode;
+        if desired_present_mode == self.present_mode {
+            return;
+        }
+
+        self.present_mode = desired_present_mode;
+
+        // 离屏渲染没有 surface，不需要处理
+        let Some(surface) = &self.context.surface else {
+            return;
+        };
+
+        let caps = surface.get_capabilities(&self.context.adapter);
+        let mut config = self.context.config.lock();
+        config.present_mode = choose_present_mode(&caps.present_modes, desired_present_mode);
+
+        info!("ACTUAL PRESENT MODE: {:?}", config.present_mode);
+
+        surface.configure(&self.context.device, &config);
+    }
+
+    /// 设置之后每一帧的清屏颜色
+    pub fn clear_background(&mut self, color: Color) {
+        self.window_config.lock().clear_color = color;
+    }
+
+    /// 设置主相机，返回的句柄可以在之后继续修改相机
+    pub fn set_main_camera(&mut self, mut camera: Camera2D) -> Arc<Mutex<Camera2D>> {
+        camera.resize(self.size);
+
+        let camera = Arc::new(Mutex::new(camera));
+        self.main_camera = Some(Arc::clone(&camera));
+
+        camera
+    }
+
+    /// 移除主相机，回到屏幕像素坐标
+    pub fn clear_main_camera(&mut self) {
+        self.main_camera = None;
+    }
+
+    pub fn main_camera(&self) -> Option<Arc<Mutex<Camera2D>>> {
+        self.main_camera.clone()
+    }
+
+    /// 直接替换主相机的句柄，用于恢复之前保存的相机
+    #[cfg(feature = "ecs")]
+    pub(crate) fn set_main_camera_handle(&mut self, camera: Option<Arc<Mutex<Camera2D>>>) {
+        if let Some(camera) = &camera {
+            camera.lock().resize(self.size);
+        }
+
+        self.main_camera = camera;
+    }
+
+    /// 屏幕像素坐标转换为主相机下的世界坐标，没有主相机时原样返回
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        match &self.main_camera {
+            Some(camera) => camera.lock().screen_to_world(point),
+            None => point,
+        }
+    }
+
+    pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
+        match &self.main_camera {
+            Some(camera) => camera.lock().world_to_screen(point),
+            None => point,
+        }
+    }
+
+    /// 1x1 白色纹理，用于绘制纯色几何体
+    pub fn white_texture(&self) -> &Texture2D {
+        &self.white_texture
+    }
+
+    /// 从 PNG / JPEG 数据创建纹理并设置采样方式，图片大小超过设备限制时返回错误
+    pub fn load_texture(
+        &self,
+        bytes: &[u8],
+        filter: FilterMode,
+    ) -> image::ImageResult<Texture2D> {
+        let image = image::load_from_memory(bytes)?.to_rgba8();
+
+        Texture2D::from_rgba8_with_filter(
+            &self.context,
+            image.width(),
+            image.height(),
+            image.as_raw(),
+            filter,
+        )
+    }
+
+    /// 本帧的合批缓冲，可以直接往里面提交自定义的几何体
+    pub fn batch(&mut self) -> &mut SpriteBatch {
+        &mut self.batch
+    }
+
+    /// 上传本帧的 uniform 数据
+    pub fn update(&mut self) {
+        self.sync_sample_count();
+        self.sync_present_mode();
+
+        let view_proj = match &self.main_camera {
+            Some(camera) => camera.lock().view_proj(),
+            // 没有主相机时使用屏幕像素坐标，左上角为原点，y 轴向下
+            None => Mat4::orthographic_rh(
+                0.0,
+                self.size.width as f32,
+                self.size.height as f32,
+                0.0,
+                -1.0,
+                1.0,
+            ),
+        };
+
+        self.context.queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::cast_slice(&[CameraUniform::new(view_proj)]),
+        );
+    }
+
+    /// 清屏并把本帧合批的内容绘制到 surface（或离屏纹理）上
+    pub fn draw(&mut self) {
+        let (output, view) = if let Some(texture) = &self.offscreen_texture {
+            (None, texture.create_view(&wgpu::TextureViewDescriptor::default()))
+        } else {
+            let Some(surface) = self.context.surface.clone() else {
+                return;
+            };
+
+            let output = match surface.get_current_texture() {
+                Ok(output) => output,
+                Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                    surface.configure(&self.context.device, &self.context.config.lock());
+                    return;
+                }
+                Err(err) => {
+                    error!("Failed to acquire next surface texture: {err:?}");
+                    return;
+                }
+            };
+
+            let view = output
+                .texture
+                .create_view(&wgpu::TextureViewDescriptor::default());
+
+            (Some(output), view)
+        };
+
+        self.batch.upload(&self.context.device, &self.context.queue);
+
+        let mut clear_color = self.window_config.lock().clear_color;
+
+        // 透明窗口的 surface 按预乘 alpha 和桌面混合
+        if self.context.config.lock().alpha_mode == wgpu::CompositeAlphaMode::PreMultiplied {
+            clear_color = clear_color.premultiplied();
+        }
+
+        // 开启 MSAA 时先画到多重采样缓冲，再 resolve 到 swapchain
+        let color_attachment = match &self.msaa_texture {
+            Some(msaa_view) => wgpu::RenderPassColorAttachment {
+                view: msaa_view,
+                resolve_target: Some(&view),
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(clear_color.into()),
+                    store: wgpu::StoreOp::Discard,
+                },
+            },
+            None => wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(clear_color.into()),
+                    store: wgpu::StoreOp::Store,
+                },
+            },
+        };
+
+        let mut encoder = self.context.device.create_command_encoder(
+            &wgpu::CommandEncoderDescriptor {
+                label: Some("frame_encoder"),
+            },
+        );
+
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("sprite_pass"),
+                color_attachments: &[Some(color_attachment)],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            if let Some(camera) = &self.main_camera {
+                let viewport = camera.lock().viewport_rect();
+                pass.set_viewport(viewport.x, viewport.y, viewport.w, viewport.h, 0.0, 1.0);
+            }
+
+            pass.set_pipeline(&self.sprite_pipeline);
+            pass.set_bind_group(0, &self.camera_bind_group, &[]);
+
+            self.batch.flush(&mut pass);
+        }
+
+        self.context.queue.submit(std::iter::once(encoder.finish()));
+
+        if let Some(output) = output {
+            output.present();
+        }
+    }
+
+    /// 读回离屏纹理中最后一帧的 RGBA8 像素，按行紧密排列；有窗口或读回失败时返回 `None`
+    pub fn read_pixels(&self) -> Option<Vec<u8>> {
+        let texture = self.offscreen_texture.as_ref()?;
+        let device = &self.context.device;
+
+        let width = texture.width();
+        let height = texture.height();
+
+        // 拷贝到 buffer 时每行需要按 256 字节对齐
+        let unpadded_bytes_per_row = width * 4;
+        let padded_bytes_per_row = unpadded_bytes_per_row
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("read_pixels_buffer"),
+            size: (padded_bytes_per_row * height) as wgpu::BufferAddress,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("read_pixels_encoder"),
+        });
+
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+
+        self.context.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        device.poll(wgpu::Maintain::Wait);
+
+        match receiver.recv() {
+            Ok(Ok(())) => (),
+            Ok(Err(err)) => {
+                error!("Failed to map read_pixels buffer: {err:?}");
+                return None;
+            }
+            Err(err) => {
+                error!("read_pixels buffer was never mapped: {err}");
+                return None;
+            }
+        }
+
+        let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks(padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+            }
+        }
+        buffer.unmap();
+
+        Some(pixels)
+    }
+
+    /// 清空本帧提交的绘制
+    pub fn end_frame(&mut self) {
+        self.batch.clear();
     }
 }
 
@@ -82,4 +529,37 @@ impl GraphicsContext {
 
         self.surface = Some(Arc::new(surface));
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ClearOnly;
+
+    impl GameLoop for ClearOnly {
+        fn start(&mut self, _c: &mut EngineContext) {}
+
+        fn update(&mut self, _c: &mut EngineContext) {}
+    }
+
+    #[test]
+    fn headless_frame_reads_back_clear_color() {
+        if !headless_adapter_available() {
+            eprintln!("skipping: no adapter available for headless rendering");
+            return;
+        }
+
+        let window_config = WindowConfig {
+            resolution: ResolutionConfig::Physical(8, 4),
+            clear_color: Color::new(1.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        };
+
+        let pixels = App::new(ClearOnly, window_config)
+            .run_headless_fixed(2, Duration::from_millis(16));
+
+        assert_eq!(pixels.len(), 8 * 4 * 4);
+        assert!(pixels.chunks(4).all(|pixel| pixel == [255, 0, 0, 255]));
+    }
+}
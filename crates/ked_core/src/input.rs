@@ -0,0 +1,351 @@
+use crate::*;
+
+use std::collections::HashSet;
+
+/// 触控板等设备的像素滚动换算成“行”时使用的比例
+const PIXELS_PER_LINE: f32 = 20.0;
+
+/// 键盘和鼠标状态，`pressed` / `released` 只在发生的那一帧为真
+#[derive(Debug)]
+pub struct Input {
+    keys_down: HashSet<KeyCode>,
+    keys_pressed: HashSet<KeyCode>,
+    keys_released: HashSet<KeyCode>,
+
+    mouse_buttons_down: HashSet<MouseButton>,
+    mouse_buttons_pressed: HashSet<MouseButton>,
+    mouse_buttons_released: HashSet<MouseButton>,
+
+    mouse_position: Vec2,
+    /// 光标进入窗口后是否已经收到过位置，没有时第一次移动不计算 delta
+    mouse_position_known: bool,
+    mouse_delta: Vec2,
+    mouse_wheel: Vec2,
+
+    touches: Touches,
+
+    input_map: InputMap,
+
+    scale_factor: f64,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self {
+            keys_down: HashSet::new(),
+            keys_pressed: HashSet::new(),
+            keys_released: HashSet::new(),
+
+            mouse_buttons_down: HashSet::new(),
+            mouse_buttons_pressed: HashSet::new(),
+            mouse_buttons_released: HashSet::new(),
+
+            mouse_position: Vec2::ZERO,
+            mouse_position_known: false,
+            mouse_delta: Vec2::ZERO,
+            mouse_wheel: Vec2::ZERO,
+
+            touches: Touches::new(),
+
+            input_map: InputMap::new(),
+
+            scale_factor: 1.0,
+        }
+    }
+
+    /// 处理一个窗口事件，由 `App::window_event` 调用，`time` 为启动以来的真实时间（秒）
+    pub fn handle_window_event(&mut self, event: &WindowEvent, time: f32) {
+        match event {
+            WindowEvent::KeyboardInput { event, .. } => {
+                if let PhysicalKey::Code(code) = event.physical_key {
+                    self.set_key(code, event.state.is_pressed());
+                }
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.set_mouse_button(*button, state.is_pressed());
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = vec2(position.x as f32, position.y as f32);
+
+                if self.mouse_position_known {
+                    self.mouse_delta += position - self.mouse_position;
+                }
+
+                self.mouse_position = position;
+                self.mouse_position_known = true;
+            }
+            // 光标离开窗口期间的移动收不到，重新进入时从新位置开始计算
+            WindowEvent::CursorEntered { .. } | WindowEvent::CursorLeft { .. } => {
+                self.mouse_position_known = false;
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.mouse_wheel += match delta {
+                    MouseScrollDelta::LineDelta(x, y) => vec2(*x, *y),
+                    MouseScrollDelta::PixelDelta(delta) => {
+                        let delta = delta.to_logical::<f32>(self.scale_factor);
+                        vec2(delta.x, delta.y) / PIXELS_PER_LINE
+                    }
+                };
+            }
+            WindowEvent::Touch(touch) => {
+                let pressure = touch.force.map_or(1.0, |force| force.normalized() as f32);
+
+                self.touches.touch(
+                    touch.id,
+                    touch.phase,
+                    vec2(touch.location.x as f32, touch.location.y as f32),
+                    pressure,
+                    time,
+                );
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.scale_factor = *scale_factor;
+            }
+            // 失去焦点时收不到松开事件，避免按键卡住
+            WindowEvent::Focused(false) => self.release_all(),
+            _ => (),
+        }
+    }
+
+    pub fn set_key(&mut self, key: KeyCode, pressed: bool) {
+        if pressed {
+            // 按住不放产生的重复事件不算新的按下
+            if self.keys_down.insert(key) {
+                self.keys_pressed.insert(key);
+            }
+        } else if self.keys_down.remove(&key) {
+            self.keys_released.insert(key);
+        }
+    }
+
+    pub fn set_mouse_button(&mut self, button: MouseButton, pressed: bool) {
+        if pressed {
+            if self.mouse_buttons_down.insert(button) {
+                self.mouse_buttons_pressed.insert(button);
+            }
+        } else if self.mouse_buttons_down.remove(&button) {
+            self.mouse_buttons_released.insert(button);
+        }
+    }
+
+    pub(crate) fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+    }
+
+    fn release_all(&mut self) {
+        self.keys_released.extend(self.keys_down.drain());
+        self.mouse_buttons_released.extend(self.mouse_buttons_down.drain());
+    }
+
+    /// 每帧更新结束后清除只持续一帧的状态
+    pub(crate) fn end_frame(&mut self) {
+        self.keys_pressed.clear();
+        self.keys_released.clear();
+
+        self.mouse_buttons_pressed.clear();
+        self.mouse_buttons_released.clear();
+
+        self.mouse_delta = Vec2::ZERO;
+        self.mouse_wheel = Vec2::ZERO;
+
+        self.touches.end_frame();
+    }
+
+    pub fn is_key_down(&self, key: KeyCode) -> bool {
+        self.keys_down.contains(&key)
+    }
+
+    pub fn is_key_pressed(&self, key: KeyCode) -> bool {
+        self.keys_pressed.contains(&key)
+    }
+
+    pub fn is_key_released(&self, key: KeyCode) -> bool {
+        self.keys_released.contains(&key)
+    }
+
+    pub fn keys_down(&self) -> impl Iterator<Item = &KeyCode> {
+        self.keys_down.iter()
+    }
+
+    pub fn keys_pressed(&self) -> impl Iterator<Item = &KeyCode> {
+        self.keys_pressed.iter()
+    }
+
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_down.contains(&button)
+    }
+
+    pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_pressed.contains(&button)
+    }
+
+    pub fn is_mouse_button_released(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_released.contains(&button)
+    }
+
+    /// 鼠标位置（物理像素），与渲染使用的屏幕坐标一致
+    pub fn mouse_position(&self) -> Vec2 {
+        self.mouse_position
+    }
+
+    /// 鼠标位置（逻辑像素），即物理像素除以窗口的缩放比例
+    pub fn mouse_position_logical(&self) -> Vec2 {
+        self.mouse_position / self.scale_factor as f32
+    }
+
+    /// 本帧鼠标移动的距离（物理像素）
+    pub fn mouse_delta(&self) -> Vec2 {
+        self.mouse_delta
+    }
+
+    /// 本帧滚轮滚动的行数，y 为正表示向上滚动
+    pub fn mouse_wheel(&self) -> Vec2 {
+        self.mouse_wheel
+    }
+
+    /// 多点触控状态和本帧识别到的手势
+    pub fn touches(&self) -> &Touches {
+        &self.touches
+    }
+
+    pub fn touches_mut(&mut self) -> &mut Touches {
+        &mut self.touches
+    }
+
+    pub fn input_map(&self) -> &InputMap {
+        &self.input_map
+    }
+
+    /// 运行时修改绑定
+    pub fn input_map_mut(&mut self) -> &mut InputMap {
+        &mut self.input_map
+    }
+
+    pub fn set_input_map(&mut self, input_map: InputMap) {
+        self.input_map = input_map;
+    }
+
+    pub fn is_action_down(&self, action: &str) -> bool {
+        self.input_map.is_action_down(self, action)
+    }
+
+    pub fn is_action_pressed(&self, action: &str) -> bool {
+        self.input_map.is_action_pressed(self, action)
+    }
+
+    pub fn is_action_released(&self, action: &str) -> bool {
+        self.input_map.is_action_released(self, action)
+    }
+
+    /// 命名轴的当前值，-1.0 到 1.0
+    pub fn axis(&self, axis: &str) -> f32 {
+        self.input_map.axis(self, axis)
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_press_hold_release() {
+        let mut input = Input::new();
+
+        input.set_key(KeyCode::Space, true);
+        assert!(input.is_key_down(KeyCode::Space));
+        assert!(input.is_key_pressed(KeyCode::Space));
+        assert!(!input.is_key_released(KeyCode::Space));
+
+        input.end_frame();
+
+        // 按住不放产生的重复事件不算新的按下
+        input.set_key(KeyCode::Space, true);
+        assert!(input.is_key_down(KeyCode::Space));
+        assert!(!input.is_key_pressed(KeyCode::Space));
+
+        input.end_frame();
+        input.set_key(KeyCode::Space, false);
+        assert!(!input.is_key_down(KeyCode::Space));
+        assert!(input.is_key_released(KeyCode::Space));
+
+        input.end_frame();
+        assert!(!input.is_key_released(KeyCode::Space));
+    }
+
+    #[test]
+    fn mouse_button_press_and_release_in_one_frame() {
+        let mut input = Input::new();
+
+        input.set_mouse_button(MouseButton::Left, true);
+        input.set_mouse_button(MouseButton::Left, false);
+
+        assert!(!input.is_mouse_button_down(MouseButton::Left));
+        assert!(input.is_mouse_button_pressed(MouseButton::Left));
+        assert!(input.is_mouse_button_released(MouseButton::Left));
+
+        input.end_frame();
+        assert!(!input.is_mouse_button_pressed(MouseButton::Left));
+        assert!(!input.is_mouse_button_released(MouseButton::Left));
+    }
+
+    #[test]
+    fn focus_lost_releases_everything() {
+        let mut input = Input::new();
+
+        input.set_key(KeyCode::KeyW, true);
+        input.set_mouse_button(MouseButton::Right, true);
+        input.end_frame();
+
+        input.handle_window_event(&WindowEvent::Focused(false), 0.0);
+
+        assert!(!input.is_key_down(KeyCode::KeyW));
+        assert!(input.is_key_released(KeyCode::KeyW));
+        assert!(input.is_mouse_button_released(MouseButton::Right));
+    }
+
+    fn cursor_moved(input: &mut Input, x: f64, y: f64) {
+        input.handle_window_event(
+            &WindowEvent::CursorMoved {
+                device_id: winit::event::DeviceId::dummy(),
+                position: PhysicalPosition::new(x, y),
+            },
+            0.0,
+        );
+    }
+
+    #[test]
+    fn mouse_delta_starts_from_first_known_position() {
+        let mut input = Input::new();
+
+        cursor_moved(&mut input, 500.0, 400.0);
+        assert_eq!(input.mouse_delta(), Vec2::ZERO);
+
+        cursor_moved(&mut input, 510.0, 395.0);
+        assert_eq!(input.mouse_delta(), vec2(10.0, -5.0));
+        input.end_frame();
+
+        input.handle_window_event(
+            &WindowEvent::CursorLeft { device_id: winit::event::DeviceId::dummy() },
+            0.0,
+        );
+        input.handle_window_event(
+            &WindowEvent::CursorEntered { device_id: winit::event::DeviceId::dummy() },
+            0.0,
+        );
+
+        // 从另一侧重新进入窗口不会产生跳变
+        cursor_moved(&mut input, 0.0, 100.0);
+        assert_eq!(input.mouse_delta(), Vec2::ZERO);
+        assert_eq!(input.mouse_position(), vec2(0.0, 100.0));
+    }
+}
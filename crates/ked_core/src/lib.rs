@@ -5,12 +5,49 @@ mod config;
 mod device;
 mod graphics;
 mod gameloop;
+mod texture;
+mod batch;
+mod pipeline;
+mod sprite;
+mod shapes;
+mod camera;
+mod snapshot;
+mod input;
+mod touch;
+mod action;
+mod limiter;
+mod scheduler;
+mod scene;
+mod plugin;
+mod events;
+mod monitor;
+mod geometry;
+#[cfg(feature = "ecs")]
+mod ecs;
 
 pub use app::*;
 pub use time::*;
 pub use color::*;
 pub use config::*;
 pub use gameloop::*;
+pub use graphics::*;
+pub use texture::*;
+pub use batch::*;
+pub use sprite::*;
+pub use camera::*;
+pub use snapshot::*;
+pub use input::*;
+pub use touch::*;
+pub use action::*;
+pub use limiter::*;
+pub use scheduler::*;
+pub use scene::*;
+pub use plugin::*;
+pub use events::*;
+pub use monitor::*;
+pub use geometry::*;
+#[cfg(feature = "ecs")]
+pub use ecs::*;
 
 pub use log;
 pub use log::*;
@@ -25,20 +62,27 @@ pub use glam::*;
 pub use winit::event_loop::*;
 pub use ked_main_macro::ked_main;
 pub use wgpu::{PresentMode, PowerPreference};
+pub use image::ImageFormat;
+pub use winit::keyboard::KeyCode;
+pub use winit::event::{MouseButton, TouchPhase, WindowEvent};
+pub use winit::dpi::{PhysicalPosition, PhysicalSize};
+pub use winit::window::{CursorGrabMode, Icon};
 
 use pollster::*;
 use parking_lot::*;
-use once_cell::sync::*;
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
 
 use device::*;
-use graphics::*;
+use pipeline::*;
 use std::sync::Arc;
+use std::time::Duration;
 
 // Winit 相关的导入
 use winit::{
     dpi::*,
     event::*,
-    event_loop::*,
+    keyboard::PhysicalKey,
     window::*
 };
 
@@ -50,6 +94,6 @@ use wgpu::{
     BindGroupDescriptor, BindGroupEntry, BindGroupLayout, 
     BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, 
     Buffer, BufferBindingType, BufferUsages, Device, DeviceDescriptor, 
-    Features, Instance, InstanceDescriptor, Limits, PipelineCompilationOptions, 
+    Instance, InstanceDescriptor, PipelineCompilationOptions, 
     Queue, ShaderStages, Surface, SurfaceConfiguration
 };
\ No newline at end of file
@@ -0,0 +1,223 @@
+use crate::*;
+
+use std::time::Instant;
+
+/// 距离下一帧小于这个时间时改为自旋等待，避免系统调度的误差
+const SPIN_THRESHOLD: Duration = Duration::from_millis(2);
+
+/// 与垂直同步无关的帧率限制
+///
+/// 窗口模式下通过 `ControlFlow::WaitUntil` 让出 CPU，最后一小段时间自旋等待；
+/// 离屏模式下直接在当前线程休眠
+#[derive(Debug)]
+pub struct FrameLimiter {
+    target_fps: Option<u32>,
+    background_fps: Option<u32>,
+    focused: bool,
+
+    last_frame: Option<Instant>,
+
+    /// 由 `App` 创建时与 `WindowConfig` 共享，修改帧率时同步写回
+    window_config: Option<Arc<Mutex<WindowConfig>>>,
+}
+
+impl FrameLimiter {
+    pub fn new(target_fps: Option<u32>, background_fps: Option<u32>) -> Self {
+        Self {
+            target_fps,
+            background_fps,
+            focused: true,
+
+            last_frame: None,
+
+            window_config: None,
+        }
+    }
+
+    /// 使用 `WindowConfig` 中的帧率设置，之后的修改会写回配置
+    pub(crate) fn with_window_config(window_config: Arc<Mutex<WindowConfig>>) -> Self {
+        let (target_fps, background_fps) = {
+            let window_config = window_config.lock();
+            (window_config.target_fps, window_config.background_fps)
+        };
+
+        Self {
+            window_config: Some(window_config),
+            ..Self::new(target_fps, background_fps)
+        }
+    }
+
+    pub fn set_target_fps(&mut self, target_fps: Option<u32>) {
+        self.target_fps = target_fps;
+
+        if let Some(window_config) = &self.window_config {
+            window_config.lock().target_fps = target_fps;
+        }
+    }
+
+    pub fn target_fps(&self) -> Option<u32> {
+        self.target_fps
+    }
+
+    /// 窗口失去焦点时使用的帧率上限
+    pub fn set_background_fps(&mut self, background_fps: Option<u32>) {
+        self.background_fps = background_fps;
+
+        if let Some(window_config) = &self.window_config {
+            window_config.lock().background_fps = background_fps;
+        }
+    }
+
+    pub fn background_fps(&self) -> Option<u32> {
+        self.background_fps
+    }
+
+    pub(crate) fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// 当前生效的两帧间隔，`None` 表示不限制
+    pub fn frame_interval(&self) -> Option<Duration> {
+        let fps = if self.focused {
+            self.target_fps
+        } else {
+            self.background_fps.or(self.target_fps)
+        };
+
+        fps.filter(|fps| *fps > 0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64))
+    }
+
+    /// 下一帧最早可以开始的时间
+    pub fn next_frame(&self) -> Option<Instant> {
+        Some(self.last_frame? + self.frame_interval()?)
+    }
+
+    /// 离下一帧还有较长时间，应该先让出 CPU
+    pub(crate) fn should_wait(&self, now: Instant) -> bool {
+        self.next_frame()
+            .is_some_and(|next_frame| next_frame > now + SPIN_THRESHOLD)
+    }
+
+    /// 根据 `App::run` 传入的 `ControlFlow` 计算本次等待应该使用的 `ControlFlow`
+    pub(crate) fn control_flow(&self, control_flow: ControlFlow) -> ControlFlow {
+        let Some(wake_up) = self.next_frame().map(|t| t - SPIN_THRESHOLD) else {
+            return control_flow;
+        };
+
+        match control_flow {
+            ControlFlow::Poll => ControlFlow::WaitUntil(wake_up),
+            ControlFlow::WaitUntil(t) => ControlFlow::WaitUntil(t.max(wake_up)),
+            ControlFlow::Wait => ControlFlow::Wait,
+        }
+    }
+
+    /// 休眠加自旋，直到可以开始下一帧
+    pub(crate) fn wait(&self) {
+        let Some(next_frame) = self.next_frame() else {
+            return;
+        };
+
+        loop {
+            let now = Instant::now();
+            if now >= next_frame {
+                break;
+            }
+
+            let remaining = next_frame - now;
+            if remaining > SPIN_THRESHOLD {
+                std::thread::sleep(remaining - SPIN_THRESHOLD);
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+    }
+
+    /// 记录一帧的开始时间
+    pub(crate) fn begin_frame(&mut self) {
+        self.begin_frame_at(Instant::now());
+    }
+
+    fn begin_frame_at(&mut self, now: Instant) {
+        // 按计划的时间推进，避免误差累积；落后超过一帧时直接从现在开始
+        self.last_frame = match (self.next_frame(), self.frame_interval()) {
+            (Some(next_frame), Some(interval)) if now < next_frame + interval => {
+                Some(next_frame.min(now))
+            }
+            _ => Some(now),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(20);
+
+    #[test]
+    fn frame_interval_uses_background_fps_when_unfocused() {
+        let mut limiter = FrameLimiter::new(Some(50), Some(10));
+        assert_eq!(limiter.frame_interval(), Some(INTERVAL));
+
+        limiter.set_focused(false);
+        assert_eq!(limiter.frame_interval(), Some(Duration::from_millis(100)));
+
+        limiter.set_background_fps(None);
+        assert_eq!(limiter.frame_interval(), Some(INTERVAL));
+
+        limiter.set_target_fps(Some(0));
+        assert_eq!(limiter.frame_interval(), None);
+    }
+
+    #[test]
+    fn control_flow_waits_until_next_frame() {
+        let start = Instant::now();
+        let mut limiter = FrameLimiter::new(Some(50), None);
+
+        // 还没有开始过任何一帧
+        assert_eq!(limiter.control_flow(ControlFlow::Poll), ControlFlow::Poll);
+
+        limiter.begin_frame_at(start);
+        let wake_up = start + INTERVAL - SPIN_THRESHOLD;
+
+        assert_eq!(limiter.next_frame(), Some(start + INTERVAL));
+        assert!(limiter.should_wait(start));
+        assert!(!limiter.should_wait(wake_up));
+
+        assert_eq!(limiter.control_flow(ControlFlow::Poll), ControlFlow::WaitUntil(wake_up));
+        assert_eq!(limiter.control_flow(ControlFlow::Wait), ControlFlow::Wait);
+
+        let later = start + INTERVAL * 2;
+        assert_eq!(limiter.control_flow(ControlFlow::WaitUntil(later)), ControlFlow::WaitUntil(later));
+    }
+
+    #[test]
+    fn begin_frame_keeps_schedule_unless_behind() {
+        let start = Instant::now();
+        let mut limiter = FrameLimiter::new(Some(50), None);
+
+        limiter.begin_frame_at(start);
+
+        // 稍晚开始的帧仍按计划时间推进，误差不会累积
+        limiter.begin_frame_at(start + INTERVAL + Duration::from_millis(3));
+        assert_eq!(limiter.next_frame(), Some(start + INTERVAL * 2));
+
+        // 落后超过一帧时从现在重新开始
+        let late = start + INTERVAL * 5;
+        limiter.begin_frame_at(late);
+        assert_eq!(limiter.next_frame(), Some(late + INTERVAL));
+    }
+
+    #[test]
+    fn fps_changes_are_written_to_window_config() {
+        let window_config = Arc::new(Mutex::new(WindowConfig::default()));
+        let mut limiter = FrameLimiter::with_window_config(Arc::clone(&window_config));
+
+        limiter.set_target_fps(Some(144));
+        limiter.set_background_fps(None);
+
+        assert_eq!(window_config.lock().target_fps, Some(144));
+        assert_eq!(window_config.lock().background_fps, None);
+    }
+}
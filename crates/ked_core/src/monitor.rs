@@ -0,0 +1,208 @@
+use crate::*;
+
+use winit::monitor::{MonitorHandle, VideoModeHandle};
+
+/// 显示器支持的一种显示模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoMode {
+    pub width: u32,
+    pub height: u32,
+    /// 刷新率（毫赫兹），60Hz 为 60000
+    pub refresh_rate_millihertz: u32,
+    pub bit_depth: u16,
+}
+
+impl VideoMode {
+    pub fn refresh_rate(&self) -> f32 {
+        self.refresh_rate_millihertz as f32 / 1000.0
+    }
+
+    /// 排序用，分辨率、刷新率、色深越高越靠前
+    fn sort_key(&self) -> (u64, u32, u16) {
+        (
+            self.width as u64 * self.height as u64,
+            self.refresh_rate_millihertz,
+            self.bit_depth,
+        )
+    }
+}
+
+impl From<&VideoModeHandle> for VideoMode {
+    fn from(mode: &VideoModeHandle) -> Self {
+        let size = mode.size();
+
+        Self {
+            width: size.width,
+            height: size.height,
+            refresh_rate_millihertz: mode.refresh_rate_millihertz(),
+            bit_depth: mode.bit_depth(),
+        }
+    }
+}
+
+/// 显示器信息，可以用来在选项菜单中列出可选的显示器和显示模式
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    /// 在 `EngineContext::monitors` 中的序号，对应 `FullscreenMode` 中的 `monitor`
+    pub index: usize,
+    pub name: Option<String>,
+    pub size: PhysicalSize<u32>,
+    /// 显示器左上角在桌面上的物理像素位置
+    pub position: PhysicalPosition<i32>,
+    pub scale_factor: f64,
+    pub refresh_rate_millihertz: Option<u32>,
+    pub is_primary: bool,
+    /// 支持的显示模式，已去重，按分辨率、刷新率从高到低排列
+    pub video_modes: Vec<VideoMode>,
+}
+
+pub(crate) fn monitor_infos(
+    monitors: &[MonitorHandle],
+    primary: Option<&MonitorHandle>,
+) -> Vec<MonitorInfo> {
+    monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let mut video_modes: Vec<VideoMode> =
+                monitor.video_modes().map(|mode| VideoMode::from(&mode)).collect();
+            sort_video_modes(&mut video_modes);
+
+            MonitorInfo {
+                index,
+                name: monitor.name(),
+                size: monitor.size(),
+                position: monitor.position(),
+                scale_factor: monitor.scale_factor(),
+                refresh_rate_millihertz: monitor.refresh_rate_millihertz(),
+                is_primary: primary == Some(monitor),
+                video_modes,
+            }
+        })
+        .collect()
+}
+
+fn sort_video_modes(modes: &mut Vec<VideoMode>) {
+    modes.sort_by_key(|mode| std::cmp::Reverse(mode.sort_key()));
+    modes.dedup();
+}
+
+/// 在 `modes` 中找到最接近 `wanted` 的模式：优先完全相同，其次分辨率相同且刷新率最接近，
+/// 没有指定或找不到同样分辨率时使用最高的模式
+fn choose_video_mode(modes: &[VideoMode], wanted: Option<VideoMode>) -> Option<usize> {
+    let best = || {
+        modes
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, mode)| mode.sort_key())
+            .map(|(i, _)| i)
+    };
+
+    let Some(wanted) = wanted else {
+        return best();
+    };
+
+    if let Some(i) = modes.iter().position(|mode| *mode == wanted) {
+        return Some(i);
+    }
+
+    let same_size = modes
+        .iter()
+        .enumerate()
+        .filter(|(_, mode)| mode.width == wanted.width && mode.height == wanted.height)
+        .min_by_key(|(_, mode)| {
+            (
+                mode.refresh_rate_millihertz.abs_diff(wanted.refresh_rate_millihertz),
+                mode.bit_depth.abs_diff(wanted.bit_depth),
+            )
+        })
+        .map(|(i, _)| i);
+
+    if same_size.is_none() {
+        warn!(
+            "Video mode {}x{} is not supported, using the highest available",
+            wanted.width, wanted.height
+        );
+    }
+
+    same_size.or_else(best)
+}
+
+/// 把 `FullscreenMode` 转换成 winit 的全屏设置
+///
+/// 序号超出范围时使用 `current` 显示器，不支持独占全屏时退回无边框全屏
+pub(crate) fn resolve_fullscreen(
+    mode: FullscreenMode,
+    monitors: &[MonitorHandle],
+    current: Option<MonitorHandle>,
+) -> Option<Fullscreen> {
+    let find_monitor = |index: Option<usize>| {
+        let index = index?;
+        let monitor = monitors.get(index).cloned();
+
+        if monitor.is_none() {
+            warn!("Monitor {index} does not exist, using the current monitor");
+        }
+
+        monitor
+    };
+
+    match mode {
+        FullscreenMode::Windowed => None,
+        FullscreenMode::Borderless { monitor } => Some(Fullscreen::Borderless(find_monitor(monitor))),
+        FullscreenMode::Exclusive { monitor, video_mode } => {
+            let Some(monitor) = find_monitor(monitor)
+                .or(current)
+                .or_else(|| monitors.first().cloned())
+            else {
+                warn!("No monitor available for exclusive fullscreen, using borderless");
+                return Some(Fullscreen::Borderless(None));
+            };
+
+            let handles: Vec<VideoModeHandle> = monitor.video_modes().collect();
+            let modes: Vec<VideoMode> = handles.iter().map(VideoMode::from).collect();
+
+            match choose_video_mode(&modes, video_mode) {
+                Some(i) => Some(Fullscreen::Exclusive(handles[i].clone())),
+                None => {
+                    warn!("Monitor has no video modes, using borderless fullscreen");
+                    Some(Fullscreen::Borderless(Some(monitor)))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(width: u32, height: u32, hz: u32) -> VideoMode {
+        VideoMode {
+            width,
+            height,
+            refresh_rate_millihertz: hz * 1000,
+            bit_depth: 32,
+        }
+    }
+
+    #[test]
+    fn video_modes_sorted_and_deduplicated() {
+        let mut modes = vec![mode(1280, 720, 60), mode(1920, 1080, 60), mode(1920, 1080, 144), mode(1280, 720, 60)];
+        sort_video_modes(&mut modes);
+
+        assert_eq!(modes, [mode(1920, 1080, 144), mode(1920, 1080, 60), mode(1280, 720, 60)]);
+    }
+
+    #[test]
+    fn choose_closest_video_mode() {
+        let modes = [mode(1280, 720, 60), mode(1920, 1080, 60), mode(1920, 1080, 144)];
+
+        assert_eq!(choose_video_mode(&modes, None), Some(2));
+        assert_eq!(choose_video_mode(&modes, Some(mode(1920, 1080, 60))), Some(1));
+        assert_eq!(choose_video_mode(&modes, Some(mode(1920, 1080, 120))), Some(2));
+        assert_eq!(choose_video_mode(&modes, Some(mode(1280, 720, 75))), Some(0));
+        assert_eq!(choose_video_mode(&modes, Some(mode(800, 600, 60))), Some(2));
+        assert_eq!(choose_video_mode(&[], None), None);
+    }
+}
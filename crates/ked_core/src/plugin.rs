@@ -0,0 +1,187 @@
+use crate::*;
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// 引擎扩展，在 `App::run` 之前通过 `App::add_plugin` 注册资源、系统和事件处理函数
+///
+/// ```no_run
+/// use ked_core::*;
+///
+/// #[derive(Default)]
+/// struct FrameCounter(u64);
+///
+/// struct FrameCounterPlugin;
+///
+/// impl Plugin for FrameCounterPlugin {
+///     fn build(&self, app: &mut App) {
+///         app.insert_resource(FrameCounter::default());
+///         app.add_system(SystemStage::PostUpdate, |c| {
+///             if let Some(counter) = c.resources.get_mut::<FrameCounter>() {
+///                 counter.0 += 1;
+///             }
+///         });
+///     }
+/// }
+/// ```
+pub trait Plugin {
+    fn build(&self, app: &mut App);
+
+    /// 用于避免同一个插件被重复添加
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// 系统在一帧中执行的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemStage {
+    /// `GameLoop::start` 之后执行一次
+    Start,
+    /// 每次 `GameLoop::fixed_update` 之后
+    FixedUpdate,
+    /// `GameLoop::update` 之前
+    PreUpdate,
+    /// `GameLoop::late_update` 之后
+    PostUpdate,
+    /// `GameLoop::render` 和场景绘制之后，提交绘制之前
+    Render,
+}
+
+type System = Box<dyn FnMut(&mut EngineContext)>;
+type EventHandler = Box<dyn FnMut(&mut EngineContext, &WindowEvent)>;
+
+/// 插件注册的系统和事件处理函数
+#[derive(Default)]
+pub(crate) struct Systems {
+    systems: Vec<(SystemStage, System)>,
+    event_handlers: Vec<EventHandler>,
+}
+
+impl Systems {
+    pub(crate) fn add_system(&mut self, stage: SystemStage, system: System) {
+        self.systems.push((stage, system));
+    }
+
+    pub(crate) fn add_event_handler(&mut self, handler: EventHandler) {
+        self.event_handlers.push(handler);
+    }
+
+    /// 按注册顺序执行某个阶段的系统
+    pub(crate) fn run(&mut self, stage: SystemStage, c: &mut EngineContext) {
+        for (system_stage, system) in self.systems.iter_mut() {
+            if *system_stage == stage {
+                system(c);
+            }
+        }
+    }
+
+    pub(crate) fn handle_event(&mut self, c: &mut EngineContext, event: &WindowEvent) {
+        for handler in self.event_handlers.iter_mut() {
+            handler(c, event);
+        }
+    }
+}
+
+/// 按类型存放的全局资源，插件和游戏代码通过 `EngineContext::resources` 共享数据
+#[derive(Default)]
+pub struct Resources {
+    map: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Resources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 插入资源，返回之前同类型的资源
+    pub fn insert<T: 'static>(&mut self, resource: T) -> Option<T> {
+        self.map
+            .insert(TypeId::of::<T>(), Box::new(resource))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|r| r.downcast_ref())
+    }
+
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.map
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|r| r.downcast_mut())
+    }
+
+    pub fn get_or_insert_with<T: 'static>(&mut self, f: impl FnOnce() -> T) -> &mut T {
+        self.map
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()))
+            .downcast_mut()
+            .expect("resource type must match its TypeId")
+    }
+
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        self.map
+            .remove(&TypeId::of::<T>())
+            .and_then(|r| r.downcast().ok())
+            .map(|r| *r)
+    }
+
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Score(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Name(&'static str);
+
+    #[test]
+    fn resources_are_stored_by_type() {
+        let mut resources = Resources::new();
+        assert!(resources.is_empty());
+
+        assert_eq!(resources.insert(Score(1)), None);
+        assert_eq!(resources.insert(Name("ked")), None);
+        assert_eq!(resources.len(), 2);
+
+        // 同类型的资源会被替换
+        assert_eq!(resources.insert(Score(2)), Some(Score(1)));
+        assert_eq!(resources.len(), 2);
+
+        resources.get_mut::<Score>().unwrap().0 += 1;
+        assert_eq!(resources.get::<Score>(), Some(&Score(3)));
+        assert_eq!(resources.get::<Name>(), Some(&Name("ked")));
+        assert!(!resources.contains::<u32>());
+
+        assert_eq!(resources.remove::<Name>(), Some(Name("ked")));
+        assert_eq!(resources.remove::<Name>(), None);
+        assert_eq!(resources.len(), 1);
+    }
+
+    #[test]
+    fn get_or_insert_with_keeps_existing_resource() {
+        let mut resources = Resources::new();
+
+        resources.get_or_insert_with(|| Score(5)).0 += 1;
+        resources.get_or_insert_with(|| Score(100)).0 += 1;
+
+        assert_eq!(resources.get::<Score>(), Some(&Score(7)));
+    }
+}
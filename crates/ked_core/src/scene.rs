@@ -0,0 +1,452 @@
+use crate::*;
+
+use std::collections::VecDeque;
+
+/// 场景栈中的一个场景，只有栈顶的场景会收到 update
+pub trait Scene {
+    /// 场景被压入栈中
+    fn enter(&mut self, _c: &mut EngineContext) {}
+
+    /// 场景从栈中移除
+    fn exit(&mut self, _c: &mut EngineContext) {}
+
+    /// 有新的场景压在了上面
+    fn pause(&mut self, _c: &mut EngineContext) {}
+
+    /// 上面的场景被弹出，重新回到栈顶
+    fn resume(&mut self, _c: &mut EngineContext) {}
+
+    fn update(&mut self, c: &mut EngineContext);
+
+    fn fixed_update(&mut self, _c: &mut EngineContext) {}
+
+    /// 每帧对栈中所有场景从下往上调用，暂停菜单下面的场景也能继续绘制
+    fn render(&mut self, _c: &mut EngineContext) {}
+}
+
+/// 切换场景时的过渡效果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    None,
+    /// 先淡出到 `color`，在中点切换场景，再淡入，`duration` 为总时长（秒）
+    Fade { duration: f32, color: Color },
+}
+
+impl Transition {
+    pub fn fade(duration: f32) -> Self {
+        Self::Fade { duration, color: BLACK }
+    }
+}
+
+enum SceneCommand {
+    Push(Box<dyn Scene>),
+    Pop,
+    Replace(Box<dyn Scene>),
+}
+
+struct ActiveTransition {
+    /// 到达中点之后为 None
+    command: Option<SceneCommand>,
+    duration: f32,
+    color: Color,
+    elapsed: f32,
+}
+
+impl ActiveTransition {
+    /// 遮罩的不透明度，中点时为 1.0
+    fn alpha(&self) -> f32 {
+        let half = self.duration / 2.0;
+        if half <= 0.0 {
+            return 0.0;
+        }
+
+        let t = self.elapsed / half;
+        if t < 1.0 { t } else { (2.0 - t).max(0.0) }
+    }
+}
+
+/// 场景栈，由 `App` 在每帧 `GameLoop::update` 之后驱动
+///
+/// 通过 `EngineContext::scenes` 调用的 push / pop / replace 不会立即生效，
+/// 而是在本帧场景更新结束后按顺序执行
+///
+/// ```no_run
+/// use ked_core::*;
+///
+/// struct Menu;
+///
+/// impl Scene for Menu {
+///     fn update(&mut self, c: &mut EngineContext) {
+///         if c.input.is_key_pressed(KeyCode::Enter) {
+///             c.scenes.replace_with(Box::new(Menu), Transition::fade(0.5));
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct SceneManager {
+    stack: Vec<Box<dyn Scene>>,
+    /// 栈中场景的数量，场景回调期间栈被暂时取出，`len` 仍然读取这个值
+    depth: usize,
+    pending: VecDeque<(SceneCommand, Transition)>,
+    transition: Option<ActiveTransition>,
+}
+
+impl SceneManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, scene: Box<dyn Scene>) {
+        self.push_with(scene, Transition::None);
+    }
+
+    pub fn pop(&mut self) {
+        self.pop_with(Transition::None);
+    }
+
+    /// 用新场景替换栈顶的场景
+    pub fn replace(&mut self, scene: Box<dyn Scene>) {
+        self.replace_with(scene, Transition::None);
+    }
+
+    pub fn push_with(&mut self, scene: Box<dyn Scene>, transition: Transition) {
+        self.pending.push_back((SceneCommand::Push(scene), transition));
+    }
+
+    pub fn pop_with(&mut self, transition: Transition) {
+        self.pending.push_back((SceneCommand::Pop, transition));
+    }
+
+    pub fn replace_with(&mut self, scene: Box<dyn Scene>, transition: Transition) {
+        self.pending.push_back((SceneCommand::Replace(scene), transition));
+    }
+
+    /// 栈中场景的数量，不包括还没有执行的切换
+    pub fn len(&self) -> usize {
+        self.depth
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depth == 0
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// 执行栈顶场景的 fixed_update
+    pub(crate) fn fixed_update(c: &mut EngineContext) {
+        let mut stack = std::mem::take(&mut c.scenes.stack);
+
+        if let Some(scene) = stack.last_mut() {
+            scene.fixed_update(c);
+        }
+
+        c.scenes.stack = stack;
+    }
+
+    /// 推进过渡，更新栈顶场景，然后执行本帧积累的切换
+    pub(crate) fn update(c: &mut EngineContext) {
+        // 过渡使用真实时间，游戏暂停时也能正常淡入淡出
+        let delta = c.timer.get_unscaled_delta_time();
+        Self::advance_transition(c, delta);
+
+        let mut stack = std::mem::take(&mut c.scenes.stack);
+
+        if let Some(scene) = stack.last_mut() {
+            scene.update(c);
+        }
+
+        c.scenes.stack = stack;
+
+        Self::apply_pending(c);
+    }
+
+    /// 从下往上绘制所有场景，最后绘制过渡遮罩
+    pub(crate) fn render(c: &mut EngineContext) {
+        let mut stack = std::mem::take(&mut c.scenes.stack);
+
+        for scene in stack.iter_mut() {
+            scene.render(c);
+        }
+
+        c.scenes.stack = stack;
+
+        let Some(transition) = &c.scenes.transition else {
+            return;
+        };
+
+        let color = Color {
+            a: transition.color.a * transition.alpha(),
+            ..transition.color
+        };
+
+        // 遮罩要盖住整个屏幕，换算到主相机下的世界坐标
+        let size = c.renderer.size;
+        let (w, h) = (size.width as f32, size.height as f32);
+        let corners = [vec2(0.0, 0.0), vec2(w, 0.0), vec2(w, h), vec2(0.0, h)]
+            .map(|p| c.renderer.screen_to_world(p));
+
+        c.renderer.draw_triangle(corners[0], corners[1], corners[2], color);
+        c.renderer.draw_triangle(corners[0], corners[2], corners[3], color);
+    }
+
+    fn advance_transition(c: &mut EngineContext, delta: f32) {
+        let Some(transition) = &mut c.scenes.transition else {
+            return;
+        };
+
+        transition.elapsed += delta;
+
+        if transition.elapsed >= transition.duration / 2.0 {
+            if let Some(command) = transition.command.take() {
+                Self::apply(c, command);
+            }
+        }
+
+        if c.scenes.transition.as_ref().is_some_and(|t| t.elapsed >= t.duration) {
+            c.scenes.transition = None;
+
+            // 过渡期间排队的切换
+            Self::apply_pending(c);
+        }
+    }
+
+    fn apply_pending(c: &mut EngineContext) {
+        while c.scenes.transition.is_none() {
+            let Some((command, transition)) = c.scenes.pending.pop_front() else {
+                break;
+            };
+
+            match transition {
+                Transition::Fade { duration, color } if duration > 0.0 => {
+                    c.scenes.transition = Some(ActiveTransition {
+                        command: Some(command),
+                        duration,
+                        color,
+                        elapsed: 0.0,
+                    });
+                }
+                _ => Self::apply(c, command),
+            }
+        }
+    }
+
+    /// 回调中看到的 `len` 已经是切换之后的数量
+    fn apply(c: &mut EngineContext, command: SceneCommand) {
+        let mut stack = std::mem::take(&mut c.scenes.stack);
+
+        match command {
+            SceneCommand::Push(mut scene) => {
+                if let Some(top) = stack.last_mut() {
+                    top.pause(c);
+                }
+
+                c.scenes.depth = stack.len() + 1;
+                scene.enter(c);
+                stack.push(scene);
+            }
+            SceneCommand::Pop => {
+                if let Some(mut top) = stack.pop() {
+                    c.scenes.depth = stack.len();
+                    top.exit(c);
+                }
+
+                if let Some(top) = stack.last_mut() {
+                    top.resume(c);
+                }
+            }
+            SceneCommand::Replace(mut scene) => {
+                let popped = stack.pop();
+                c.scenes.depth = stack.len() + 1;
+
+                if let Some(mut top) = popped {
+                    top.exit(c);
+                }
+
+                scene.enter(c);
+                stack.push(scene);
+            }
+        }
+
+        c.scenes.stack = stack;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Log = Arc<Mutex<Vec<String>>>;
+    type Frame = Box<dyn FnMut(&mut EngineContext, &Log)>;
+
+    struct Logged {
+        name: &'static str,
+        log: Log,
+    }
+
+    impl Logged {
+        fn boxed(name: &'static str, log: &Log) -> Box<dyn Scene> {
+            Box::new(Self { name, log: Arc::clone(log) })
+        }
+
+        fn record(&self, event: &str, c: &EngineContext) {
+            self.log.lock().push(format!("{} {event} {}", self.name, c.scenes.len()));
+        }
+    }
+
+    impl Scene for Logged {
+        fn enter(&mut self, c: &mut EngineContext) {
+            self.record("enter", c);
+        }
+
+        fn exit(&mut self, c: &mut EngineContext) {
+            self.record("exit", c);
+        }
+
+        fn pause(&mut self, c: &mut EngineContext) {
+            self.record("pause", c);
+        }
+
+        fn resume(&mut self, c: &mut EngineContext) {
+            self.record("resume", c);
+        }
+
+        fn update(&mut self, c: &mut EngineContext) {
+            self.record("update", c);
+        }
+    }
+
+    /// 第 n 帧执行 `frames[n]`，并记录场景栈的状态
+    struct Script {
+        log: Log,
+        frames: Vec<Frame>,
+        frame: usize,
+    }
+
+    impl GameLoop for Script {
+        fn start(&mut self, _c: &mut EngineContext) {}
+
+        fn update(&mut self, c: &mut EngineContext) {
+            self.log.lock().push(format!(
+                "frame {} {} {}",
+                self.frame,
+                c.scenes.len(),
+                c.scenes.is_transitioning()
+            ));
+
+            if let Some(frame) = self.frames.get_mut(self.frame) {
+                frame(c, &self.log);
+            }
+
+            self.frame += 1;
+        }
+    }
+
+    fn run_script(frames: Vec<Frame>) -> Option<Vec<String>> {
+        if !headless_adapter_available() {
+            eprintln!("skipping: no adapter available for headless rendering");
+            return None;
+        }
+
+        let log = Log::default();
+        let count = frames.len() as u32 + 1;
+        let script = Script { log: Arc::clone(&log), frames, frame: 0 };
+
+        let window_config = WindowConfig {
+            resolution: ResolutionConfig::Physical(8, 8),
+            ..Default::default()
+        };
+
+        // 1/64 秒可以用浮点数精确表示，过渡的中点不会有误差
+        App::new(script, window_config)
+            .run_headless_fixed(count, Duration::from_secs_f64(1.0 / 64.0));
+
+        let log = log.lock().clone();
+        Some(log)
+    }
+
+    #[test]
+    fn push_replace_pop_in_order() {
+        let Some(log) = run_script(vec![
+            Box::new(|c, log| {
+                c.scenes.push(Logged::boxed("a", log));
+                c.scenes.push(Logged::boxed("b", log));
+            }),
+            Box::new(|c, log| c.scenes.replace(Logged::boxed("c", log))),
+            Box::new(|c, _| c.scenes.pop()),
+        ]) else {
+            return;
+        };
+
+        assert_eq!(log, [
+            "frame 0 0 false",
+            "a enter 1",
+            "a pause 1",
+            "b enter 2",
+            "frame 1 2 false",
+            "b update 2",
+            "b exit 2",
+            "c enter 2",
+            "frame 2 2 false",
+            "c update 2",
+            "c exit 1",
+            "a resume 1",
+            "frame 3 1 false",
+            "a update 1",
+        ]);
+    }
+
+    #[test]
+    fn fade_switches_at_midpoint_and_queues_commands() {
+        let fade = Transition::Fade { duration: 4.0 / 64.0, color: BLACK };
+
+        let Some(log) = run_script(vec![
+            Box::new(move |c, log| {
+                c.scenes.push_with(Logged::boxed("a", log), fade);
+                c.scenes.push(Logged::boxed("b", log));
+            }),
+            Box::new(|_, _| ()),
+            Box::new(|_, _| ()),
+            Box::new(|_, _| ()),
+            Box::new(|_, _| ()),
+        ]) else {
+            return;
+        };
+
+        assert_eq!(log, [
+            "frame 0 0 false",
+            "frame 1 0 true",
+            "frame 2 0 true",
+            "a enter 1",
+            "a update 1",
+            "frame 3 1 true",
+            "a update 1",
+            "frame 4 1 true",
+            "a pause 1",
+            "b enter 2",
+            "b update 2",
+            "frame 5 2 false",
+            "b update 2",
+        ]);
+    }
+
+    #[test]
+    fn transition_alpha_peaks_at_midpoint() {
+        let mut transition = ActiveTransition {
+            command: None,
+            duration: 1.0,
+            color: BLACK,
+            elapsed: 0.0,
+        };
+
+        for (elapsed, alpha) in [(0.0, 0.0), (0.25, 0.5), (0.5, 1.0), (0.75, 0.5), (1.0, 0.0), (2.0, 0.0)] {
+            transition.elapsed = elapsed;
+            assert_eq!(transition.alpha(), alpha, "elapsed {elapsed}");
+        }
+
+        transition.duration = 0.0;
+        assert_eq!(transition.alpha(), 0.0);
+    }
+}
@@ -0,0 +1,484 @@
+use crate::*;
+
+/// 倒计时，可以只触发一次，也可以循环触发
+///
+/// 由游戏代码自己持有，每帧用 `tick` 推进
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timer {
+    duration: f32,
+    elapsed: f32,
+    repeating: bool,
+    paused: bool,
+
+    finished: bool,
+    /// 本次 `tick` 中完成的次数，循环计时器一帧可能完成多次
+    times_finished: u32,
+}
+
+impl Timer {
+    /// 经过 `seconds` 秒后完成一次
+    pub fn countdown(seconds: f32) -> Self {
+        Self::new(seconds, false)
+    }
+
+    /// 每隔 `seconds` 秒完成一次
+    pub fn repeating(seconds: f32) -> Self {
+        Self::new(seconds, true)
+    }
+
+    fn new(seconds: f32, repeating: bool) -> Self {
+        Self {
+            duration: seconds.max(0.0),
+            elapsed: 0.0,
+            repeating,
+            paused: false,
+
+            finished: false,
+            times_finished: 0,
+        }
+    }
+
+    /// 推进 `delta` 秒
+    pub fn tick(&mut self, delta: f32) -> &Self {
+        self.times_finished = 0;
+
+        if self.paused || (self.finished && !self.repeating) {
+            return self;
+        }
+
+        self.elapsed += delta.max(0.0);
+
+        if self.elapsed < self.duration {
+            return self;
+        }
+
+        self.finished = true;
+
+        if !self.repeating {
+            self.elapsed = self.duration;
+            self.times_finished = 1;
+        } else if self.duration > 0.0 {
+            self.times_finished = (self.elapsed / self.duration) as u32;
+            self.elapsed %= self.duration;
+        } else {
+            self.times_finished = 1;
+            self.elapsed = 0.0;
+        }
+
+        self
+    }
+
+    /// 按游戏时间推进，受时间缩放和暂停影响
+    pub fn update(&mut self, time: &Time) -> &Self {
+        self.tick(time.get_delta_time())
+    }
+
+    /// 至少完成过一次
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// 在最近一次 `tick` 中完成
+    pub fn just_finished(&self) -> bool {
+        self.times_finished > 0
+    }
+
+    pub fn times_finished(&self) -> u32 {
+        self.times_finished
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn remaining(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    pub fn set_duration(&mut self, seconds: f32) {
+        self.duration = seconds.max(0.0);
+    }
+
+    /// 进度，0.0 到 1.0
+    pub fn fraction(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    pub fn is_repeating(&self) -> bool {
+        self.repeating
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.finished = false;
+        self.times_finished = 0;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+/// 循环任务一帧最多补执行的次数，更多的周期会被丢弃，避免卡顿之后越追越慢
+const MAX_CATCH_UP_RUNS: u32 = 5;
+
+/// `Scheduler` 中任务的编号，用于取消任务
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+enum Callback {
+    Once(Box<dyn FnOnce(&mut EngineContext)>),
+    Repeat(Box<dyn FnMut(&mut EngineContext)>),
+}
+
+pub(crate) struct Task {
+    id: TaskId,
+    /// 距离下次触发的时间（秒），可能为负
+    remaining: f32,
+    interval: f32,
+    callback: Callback,
+}
+
+impl Task {
+    pub(crate) fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// 执行到期的任务，循环任务返回自身以便重新加入调度
+    pub(crate) fn run(mut self, c: &mut EngineContext) -> Option<Self> {
+        match self.callback {
+            Callback::Once(callback) => {
+                callback(c);
+
+                None
+            }
+            Callback::Repeat(ref mut callback) => {
+                // 一帧跨过多个周期时补上漏掉的次数，最多 MAX_CATCH_UP_RUNS 次
+                for _ in 0..MAX_CATCH_UP_RUNS {
+                    callback(c);
+
+                    self.remaining += self.interval;
+
+                    // 回调中可能取消了自己
+                    if self.remaining > 0.0 || self.interval <= 0.0 || c.scheduler.is_cancelled(self.id) {
+                        break;
+                    }
+                }
+
+                // 丢弃剩下的周期，保持原来的节奏
+                if self.remaining <= 0.0 && self.interval > 0.0 {
+                    self.remaining = self.remaining.rem_euclid(self.interval);
+                }
+
+                self.remaining = self.remaining.max(0.0);
+
+                Some(self)
+            }
+        }
+    }
+}
+
+/// 按游戏时间延迟或循环执行回调，由 `App` 持有，在每帧 `update` 之前执行到期的任务
+///
+/// ```no_run
+/// use ked_core::*;
+///
+/// fn start(c: &mut EngineContext) {
+///     c.scheduler.after(2.0, |_| info!("two seconds later"));
+///     c.scheduler.every(0.5, |c| info!("tick at {}", c.timer.get_time()));
+/// }
+/// ```
+#[derive(Default)]
+pub struct Scheduler {
+    next_id: u64,
+    tasks: Vec<Task>,
+    /// 本帧已经取出、正在执行的任务
+    running: Vec<TaskId>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `seconds` 秒后执行一次
+    pub fn after(&mut self, seconds: f32, callback: impl FnOnce(&mut EngineContext) + 'static) -> TaskId {
+        self.schedule(seconds, 0.0, Callback::Once(Box::new(callback)))
+    }
+
+    /// 每隔 `seconds` 秒执行一次，第一次在 `seconds` 秒后
+    ///
+    /// 一帧跨过多个周期时会补执行，但最多 `MAX_CATCH_UP_RUNS`（5）次
+    pub fn every(&mut self, seconds: f32, callback: impl FnMut(&mut EngineContext) + 'static) -> TaskId {
+        self.schedule(seconds, seconds.max(0.0), Callback::Repeat(Box::new(callback)))
+    }
+
+    fn schedule(&mut self, delay: f32, interval: f32, callback: Callback) -> TaskId {
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+
+        self.tasks.push(Task {
+            id,
+            remaining: delay.max(0.0),
+            interval,
+            callback,
+        });
+
+        id
+    }
+
+    /// 取消任务，任务不存在或已经执行完时返回 false
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        if let Some(index) = self.running.iter().position(|t| *t == id) {
+            self.running.swap_remove(index);
+
+            return true;
+        }
+
+        let len = self.tasks.len();
+        self.tasks.retain(|t| t.id != id);
+
+        self.tasks.len() != len
+    }
+
+    pub fn is_scheduled(&self, id: TaskId) -> bool {
+        self.running.contains(&id) || self.tasks.iter().any(|t| t.id == id)
+    }
+
+    /// 距离任务下次执行的时间（秒）
+    pub fn remaining(&self, id: TaskId) -> Option<f32> {
+        self.tasks
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| t.remaining.max(0.0))
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.len() + self.running.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+        self.running.clear();
+    }
+
+    /// 推进 `delta` 秒并取出到期的任务
+    pub(crate) fn take_due(&mut self, delta: f32) -> Vec<Task> {
+        let mut due = Vec::new();
+        let mut index = 0;
+
+        while index < self.tasks.len() {
+            self.tasks[index].remaining -= delta;
+
+            if self.tasks[index].remaining <= 0.0 {
+                let task = self.tasks.swap_remove(index);
+                self.running.push(task.id);
+                due.push(task);
+            } else {
+                index += 1;
+            }
+        }
+
+        // swap_remove 会打乱顺序，按创建顺序执行
+        due.sort_by_key(|t| t.id.0);
+
+        due
+    }
+
+    /// 把执行完的循环任务放回去，执行期间被取消的任务会被丢弃
+    pub(crate) fn finish(&mut self, id: TaskId, task: Option<Task>) {
+        let Some(index) = self.running.iter().position(|t| *t == id) else {
+            return;
+        };
+
+        self.running.swap_remove(index);
+
+        if let Some(task) = task {
+            self.tasks.push(task);
+        }
+    }
+
+    /// 任务执行前已经被取消
+    pub(crate) fn is_cancelled(&self, id: TaskId) -> bool {
+        !self.running.contains(&id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn countdown_finishes_once() {
+        let mut timer = Timer::countdown(1.0);
+
+        assert!(!timer.tick(0.6).just_finished());
+        assert!(timer.tick(0.6).just_finished());
+        assert!(timer.finished());
+        assert_eq!(timer.remaining(), 0.0);
+
+        assert!(!timer.tick(5.0).just_finished());
+        assert!(timer.finished());
+
+        timer.reset();
+        assert!(!timer.finished());
+        assert_eq!(timer.fraction(), 0.0);
+    }
+
+    #[test]
+    fn repeating_timer_counts_every_period() {
+        let mut timer = Timer::repeating(0.5);
+
+        assert_eq!(timer.tick(1.25).times_finished(), 2);
+        assert!((timer.elapsed() - 0.25).abs() < 1e-6);
+
+        assert_eq!(timer.tick(0.25).times_finished(), 1);
+        assert_eq!(timer.tick(0.1).times_finished(), 0);
+    }
+
+    #[test]
+    fn paused_timer_does_not_advance() {
+        let mut timer = Timer::countdown(1.0);
+
+        timer.pause();
+        timer.tick(2.0);
+        assert!(!timer.finished());
+
+        timer.resume();
+        assert!(timer.tick(1.0).just_finished());
+    }
+
+    #[test]
+    fn scheduler_takes_due_tasks_in_order() {
+        let mut scheduler = Scheduler::new();
+
+        let late = scheduler.after(2.0, |_| ());
+        let first = scheduler.after(0.5, |_| ());
+        let second = scheduler.every(1.0, |_| ());
+
+        assert!(scheduler.take_due(0.4).is_empty());
+
+        let due = scheduler.take_due(0.6);
+        assert_eq!(due.iter().map(|t| t.id()).collect::<Vec<_>>(), [first, second]);
+        assert!(scheduler.is_scheduled(second));
+
+        // 执行期间取消的任务不会再放回去
+        assert!(scheduler.cancel(second));
+        for task in due {
+            scheduler.finish(task.id(), None);
+        }
+
+        assert!(!scheduler.is_scheduled(second));
+        assert_eq!(scheduler.len(), 1);
+        assert!((scheduler.remaining(late).unwrap() - 1.0).abs() < 1e-6);
+    }
+
+    /// 在 `start` 中安排任务，每帧固定前进 `delta` 秒，返回回调留下的记录
+    fn run_tasks(
+        frames: u32,
+        delta: f32,
+        schedule: impl FnMut(&mut EngineContext, &Arc<Mutex<Vec<u32>>>) + 'static,
+    ) -> Option<Vec<u32>> {
+        struct Tasks<F> {
+            log: Arc<Mutex<Vec<u32>>>,
+            schedule: F,
+        }
+
+        impl<F: FnMut(&mut EngineContext, &Arc<Mutex<Vec<u32>>>)> GameLoop for Tasks<F> {
+            fn start(&mut self, c: &mut EngineContext) {
+                (self.schedule)(c, &self.log);
+            }
+
+            fn update(&mut self, _c: &mut EngineContext) {}
+        }
+
+        if !headless_adapter_available() {
+            eprintln!("skipping: no adapter available for headless rendering");
+            return None;
+        }
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let tasks = Tasks { log: Arc::clone(&log), schedule };
+        let window_config = WindowConfig {
+            resolution: ResolutionConfig::Physical(8, 8),
+            ..Default::default()
+        };
+
+        App::new(tasks, window_config).run_headless_fixed(frames, Duration::from_secs_f32(delta));
+
+        let log = log.lock().clone();
+        Some(log)
+    }
+
+    #[test]
+    fn callbacks_run_when_due() {
+        let Some(log) = run_tasks(4, 0.25, |c, log| {
+            let once = Arc::clone(log);
+            c.scheduler.after(0.5, move |c| once.lock().push(c.timer.get_frame_count() as u32));
+
+            let repeat = Arc::clone(log);
+            c.scheduler.every(0.75, move |_| repeat.lock().push(100));
+        }) else {
+            return;
+        };
+
+        // 第 2 帧到达 0.5 秒，第 3 帧到达 0.75 秒
+        assert_eq!(log, [2, 100]);
+    }
+
+    #[test]
+    fn repeat_catch_up_is_capped() {
+        let Some(log) = run_tasks(2, 1.0, |c, log| {
+            let log = Arc::clone(log);
+            c.scheduler.every(0.001, move |_| log.lock().push(1));
+        }) else {
+            return;
+        };
+
+        assert_eq!(log.len() as u32, MAX_CATCH_UP_RUNS * 2);
+    }
+
+    #[test]
+    fn repeat_can_cancel_itself() {
+        let Some(log) = run_tasks(3, 1.0, |c, log| {
+            let log = Arc::clone(log);
+            let id = Rc::new(Cell::new(None));
+            let task = Rc::clone(&id);
+
+            id.set(Some(c.scheduler.every(0.25, move |c| {
+                log.lock().push(1);
+
+                if log.lock().len() == 2 {
+                    assert!(c.scheduler.cancel(task.get().unwrap()));
+                }
+            })));
+        }) else {
+            return;
+        };
+
+        assert_eq!(log, [1, 1]);
+    }
+}
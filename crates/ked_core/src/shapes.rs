@@ -0,0 +1,96 @@
+/*
+ * source: https://github.com/not-fl3/macroquad
+ */
+
+use crate::*;
+
+impl WgpuRenderer {
+    /// 用纯色几何体提交到合批缓冲
+    fn push_shape(&mut self, vertices: &[Vertex], indices: &[u32]) {
+        let white = self.white_texture().clone();
+        self.batch().push(&white, vertices, indices);
+    }
+
+    /// Draws a solid rectangle with its top-left corner at `[x, y]` with size `[w, h]` (width going to the right, height going down), with a given `color`.
+    pub fn draw_rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.push_shape(
+            &[
+                Vertex::new(x, y, 0.0, 0.0, color),
+                Vertex::new(x + w, y, 1.0, 0.0, color),
+                Vertex::new(x + w, y + h, 1.0, 1.0, color),
+                Vertex::new(x, y + h, 0.0, 1.0, color),
+            ],
+            &[0, 1, 2, 0, 2, 3],
+        );
+    }
+
+    /// Draws a solid triangle between points `v1`, `v2`, and `v3` with a given `color`.
+    pub fn draw_triangle(&mut self, v1: Vec2, v2: Vec2, v3: Vec2, color: Color) {
+        self.push_shape(
+            &[
+                Vertex::new(v1.x, v1.y, 0.0, 0.0, color),
+                Vertex::new(v2.x, v2.y, 0.0, 0.0, color),
+                Vertex::new(v3.x, v3.y, 0.0, 0.0, color),
+            ],
+            &[0, 1, 2],
+        );
+    }
+
+    /// Draws a line between points `[x1, y1]` and `[x2, y2]` with a given `thickness` and `color`.
+    pub fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        let dx = x2 - x1;
+        let dy = y2 - y1;
+
+        // https://stackoverflow.com/questions/1243614/how-do-i-calculate-the-normal-vector-of-a-line-segment
+        let nx = -dy;
+        let ny = dx;
+
+        let tlen = (nx * nx + ny * ny).sqrt() / (thickness * 0.5);
+        if tlen < f32::EPSILON {
+            return;
+        }
+        let tx = nx / tlen;
+        let ty = ny / tlen;
+
+        self.push_shape(
+            &[
+                Vertex::new(x1 + tx, y1 + ty, 0.0, 0.0, color),
+                Vertex::new(x1 - tx, y1 - ty, 0.0, 0.0, color),
+                Vertex::new(x2 + tx, y2 + ty, 0.0, 0.0, color),
+                Vertex::new(x2 - tx, y2 - ty, 0.0, 0.0, color),
+            ],
+            &[0, 1, 2, 2, 1, 3],
+        );
+    }
+
+    /// Draws a solid regular polygon centered at `[x, y]` with a given number of `sides`, `radius`, clockwise `rotation` (in degrees) and `color`.
+    ///
+    /// Unlike `DrawTextureParams::rotation` and `Camera2D::rotation`, which are in radians, `rotation` here is in degrees.
+    pub fn draw_poly(&mut self, x: f32, y: f32, sides: u8, radius: f32, rotation: f32, color: Color) {
+        if sides < 3 {
+            return;
+        }
+
+        let rot = rotation.to_radians();
+
+        let mut vertices = Vec::<Vertex>::with_capacity(sides as usize + 1);
+        let mut indices = Vec::<u32>::with_capacity(sides as usize * 3);
+
+        vertices.push(Vertex::new(x, y, 0.0, 0.0, color));
+        for i in 0..sides as u32 {
+            let rx = (i as f32 / sides as f32 * std::f32::consts::PI * 2. + rot).cos();
+            let ry = (i as f32 / sides as f32 * std::f32::consts::PI * 2. + rot).sin();
+
+            vertices.push(Vertex::new(x + radius * rx, y + radius * ry, rx, ry, color));
+
+            indices.extend_from_slice(&[0, i + 1, (i + 1) % sides as u32 + 1]);
+        }
+
+        self.push_shape(&vertices, &indices);
+    }
+
+    /// Draws a solid circle centered at `[x, y]` with a given radius `r` and `color`.
+    pub fn draw_circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        self.draw_poly(x, y, 20, r, 0., color);
+    }
+}
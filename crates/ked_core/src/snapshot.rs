@@ -0,0 +1,293 @@
+use crate::*;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use image::RgbaImage;
+
+/// 设置这个环境变量后，快照测试会用当前画面覆盖参考图
+pub const UPDATE_SNAPSHOTS_ENV: &str = "KED_UPDATE_SNAPSHOTS";
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Image(image::ImageError),
+    Io(std::io::Error),
+    /// 参考图不存在，设置 `KED_UPDATE_SNAPSHOTS` 后重新运行即可生成
+    MissingReference(PathBuf),
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    Mismatch {
+        mismatched_pixels: usize,
+        diff_path: PathBuf,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Image(err) => write!(f, "snapshot image error: {err}"),
+            Self::Io(err) => write!(f, "snapshot io error: {err}"),
+            Self::MissingReference(path) => write!(
+                f,
+                "snapshot reference {} does not exist, run with {UPDATE_SNAPSHOTS_ENV}=1 to create it",
+                path.display()
+            ),
+            Self::SizeMismatch { expected, actual } => write!(
+                f,
+                "snapshot size mismatch: expected {}x{}, got {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            Self::Mismatch { mismatched_pixels, diff_path } => write!(
+                f,
+                "{mismatched_pixels} pixels differ from the reference, see {}",
+                diff_path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<image::ImageError> for SnapshotError {
+    fn from(err: image::ImageError) -> Self {
+        Self::Image(err)
+    }
+}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// 两张图片逐像素比较的结果
+pub struct ImageDiff {
+    pub mismatched_pixels: usize,
+    /// 不一致的像素标为红色，其余为参考图变暗后的灰度
+    pub diff: RgbaImage,
+}
+
+/// 任意通道的差值超过 `tolerance` 即认为该像素不一致，两张图尺寸必须相同
+pub fn compare_images(expected: &RgbaImage, actual: &RgbaImage, tolerance: u8) -> ImageDiff {
+    assert_eq!(expected.dimensions(), actual.dimensions());
+
+    let mut mismatched_pixels = 0;
+    let mut diff = RgbaImage::new(expected.width(), expected.height());
+
+    for ((e, a), d) in expected.pixels().zip(actual.pixels()).zip(diff.pixels_mut()) {
+        let differs = e.0.iter()
+            .zip(a.0.iter())
+            .any(|(e, a)| e.abs_diff(*a) > tolerance);
+
+        *d = if differs {
+            mismatched_pixels += 1;
+            image::Rgba([255, 0, 0, 255])
+        } else {
+            let luma = ((e[0] as u32 + e[1] as u32 + e[2] as u32) / 3 / 4) as u8;
+            image::Rgba([luma, luma, luma, 255])
+        };
+    }
+
+    ImageDiff { mismatched_pixels, diff }
+}
+
+/// 离屏渲染一个 `GameLoop` 并与保存的参考图比较
+pub struct SnapshotTest {
+    pub name: String,
+    /// 参考图所在目录，默认是 `$CARGO_MANIFEST_DIR/tests/snapshots`
+    pub reference_dir: PathBuf,
+    /// 失败时写入实际画面和差异图的目录，默认是 `$CARGO_TARGET_DIR/snapshots`，
+    /// 没有设置时使用工作区的 target 目录
+    pub output_dir: PathBuf,
+    pub frames: u32,
+    /// 每帧固定前进的时间
+    pub delta_time: Duration,
+    /// 每个通道允许的误差
+    pub tolerance: u8,
+    pub window_config: WindowConfig,
+}
+
+impl SnapshotTest {
+    pub fn new(name: impl Into<String>) -> Self {
+        let root = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+
+        let window_config = WindowConfig {
+            resolution: ResolutionConfig::Physical(256, 256),
+            ..Default::default()
+        };
+
+        Self {
+            name: name.into(),
+            reference_dir: root.join("tests").join("snapshots"),
+            output_dir: target_dir(&root).join("snapshots"),
+            frames: 1,
+            delta_time: Duration::from_secs_f64(1.0 / 60.0),
+            tolerance: 2,
+            window_config,
+        }
+    }
+
+    pub fn reference_path(&self) -> PathBuf {
+        self.reference_dir.join(format!("{}.png", self.name))
+    }
+
+    /// 渲染并返回最后一帧
+    pub fn render(&self, game: impl GameLoop + 'static) -> RgbaImage {
+        let (width, height) = (
+            self.window_config.resolution.width(),
+            self.window_config.resolution.height(),
+        );
+
+        let pixels = App::new(game, self.window_config.clone())
+            .run_headless_fixed(self.frames, self.delta_time);
+
+        RgbaImage::from_raw(width, height, pixels)
+            .expect("headless frame must match the configured resolution")
+    }
+
+    /// 设置了 `KED_UPDATE_SNAPSHOTS` 时写入新的参考图，否则与参考图比较，参考图不存在时返回错误
+    pub fn run(&self, game: impl GameLoop + 'static) -> Result<(), SnapshotError> {
+        let actual = self.render(game);
+        let reference_path = self.reference_path();
+
+        if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+            save_png(&actual, &reference_path)?;
+            info!("Saved snapshot reference {}", reference_path.display());
+
+            return Ok(());
+        }
+
+        if !reference_path.exists() {
+            return Err(SnapshotError::MissingReference(reference_path));
+        }
+
+        let expected = image::open(&reference_path)?.to_rgba8();
+
+        if expected.dimensions() != actual.dimensions() {
+            return Err(SnapshotError::SizeMismatch {
+                expected: expected.dimensions(),
+                actual: actual.dimensions(),
+            });
+        }
+
+        let result = compare_images(&expected, &actual, self.tolerance);
+        if result.mismatched_pixels == 0 {
+            return Ok(());
+        }
+
+        let actual_path = self.output_dir.join(format!("{}.actual.png", self.name));
+        let diff_path = self.output_dir.join(format!("{}.diff.png", self.name));
+
+        save_png(&actual, &actual_path)?;
+        save_png(&result.diff, &diff_path)?;
+
+        Err(SnapshotError::Mismatch {
+            mismatched_pixels: result.mismatched_pixels,
+            diff_path,
+        })
+    }
+}
+
+/// 用默认设置运行快照测试，失败时 panic
+pub fn assert_snapshot(name: &str, frames: u32, game: impl GameLoop + 'static) {
+    let mut test = SnapshotTest::new(name);
+    test.frames = frames;
+
+    if let Err(err) = test.run(game) {
+        panic!("snapshot `{name}` failed: {err}");
+    }
+}
+
+/// `CARGO_TARGET_DIR`，没有设置时向上查找工作区根目录（有 Cargo.lock 的目录）下的 target
+fn target_dir(manifest_dir: &Path) -> PathBuf {
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    manifest_dir
+        .ancestors()
+        .find(|dir| dir.join("Cargo.lock").is_file())
+        .unwrap_or(manifest_dir)
+        .join("target")
+}
+
+fn save_png(image: &RgbaImage, path: &Path) -> Result<(), SnapshotError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    image.save_with_format(path, ImageFormat::Png)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, pixel: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, image::Rgba(pixel))
+    }
+
+    #[test]
+    fn compare_images_respects_tolerance() {
+        let expected = solid(4, 4, [100, 100, 100, 255]);
+        let mut actual = solid(4, 4, [102, 99, 100, 255]);
+
+        assert_eq!(compare_images(&expected, &actual, 2).mismatched_pixels, 0);
+        assert_eq!(compare_images(&expected, &actual, 1).mismatched_pixels, 16);
+
+        actual.put_pixel(1, 2, image::Rgba([0, 100, 100, 255]));
+        let result = compare_images(&expected, &actual, 2);
+
+        assert_eq!(result.mismatched_pixels, 1);
+        assert_eq!(result.diff.get_pixel(1, 2).0, [255, 0, 0, 255]);
+        assert_eq!(result.diff.get_pixel(0, 0).0, [25, 25, 25, 255]);
+    }
+
+    struct Squares;
+
+    impl GameLoop for Squares {
+        fn start(&mut self, _c: &mut EngineContext) {}
+
+        fn update(&mut self, _c: &mut EngineContext) {}
+
+        fn render(&mut self, c: &mut EngineContext) {
+            c.draw_rectangle(8.0, 8.0, 24.0, 24.0, Color::new(1.0, 0.0, 0.0, 1.0));
+            c.draw_rectangle(24.0, 24.0, 32.0, 16.0, Color::new(0.0, 1.0, 0.0, 1.0));
+        }
+    }
+
+    #[test]
+    fn squares_match_reference() {
+        if !headless_adapter_available() {
+            eprintln!("skipping: no adapter available for headless rendering");
+            return;
+        }
+
+        let mut test = SnapshotTest::new("squares");
+        test.window_config.resolution = ResolutionConfig::Physical(64, 64);
+        test.window_config.sample_count = Msaa::Off;
+        test.window_config.clear_color = Color::new(0.0, 0.0, 0.0, 1.0);
+
+        if let Err(err) = test.run(Squares) {
+            panic!("{err}");
+        }
+    }
+
+    #[test]
+    fn missing_reference_fails() {
+        if !headless_adapter_available() || std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+            return;
+        }
+
+        let mut test = SnapshotTest::new("does_not_exist");
+        test.window_config.resolution = ResolutionConfig::Physical(8, 8);
+
+        assert!(matches!(test.run(Squares), Err(SnapshotError::MissingReference(_))));
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ * source: https://github.com/not-fl3/macroquad
+ */
+
+use crate::*;
+
+/// A 2D rectangle, defined by its top-left corner, width and height.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl Rect {
+    pub const fn new(x: f32, y: f32, w: f32, h: f32) -> Rect {
+        Rect { x, y, w, h }
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.x
+            && point.x < self.x + self.w
+            && point.y >= self.y
+            && point.y < self.y + self.h
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DrawTextureParams {
+    /// 在屏幕上绘制的尺寸，默认为源区域（或整张纹理）的尺寸
+    pub dest_size: Option<Vec2>,
+
+    /// 纹理中要绘制的部分（像素），默认为整张纹理
+    pub source: Option<Rect>,
+
+    /// 旋转角度（弧度）
+    pub rotation: f32,
+
+    /// 水平镜像
+    pub flip_x: bool,
+
+    /// 垂直镜像
+    pub flip_y: bool,
+
+    /// 旋转中心，默认为绘制区域的中心
+    pub pivot: Option<Vec2>,
+}
+
+impl WgpuRenderer {
+    pub fn draw_texture(&mut self, texture: &Texture2D, x: f32, y: f32, color: Color) {
+        self.draw_texture_ex(texture, x, y, color, DrawTextureParams::default());
+    }
+
+    pub fn draw_texture_ex(
+        &mut self,
+        texture: &Texture2D,
+        x: f32,
+        y: f32,
+        color: Color,
+        params: DrawTextureParams,
+    ) {
+        let texture_size = texture.size();
+
+        let Rect { x: sx, y: sy, w: sw, h: sh } = params
+            .source
+            .unwrap_or(Rect::new(0., 0., texture_size.x, texture_size.y));
+
+        let mut u0 = sx / texture_size.x;
+        let mut v0 = sy / texture_size.y;
+        let mut u1 = (sx + sw) / texture_size.x;
+        let mut v1 = (sy + sh) / texture_size.y;
+
+        if params.flip_x {
+            std::mem::swap(&mut u0, &mut u1);
+        }
+        if params.flip_y {
+            std::mem::swap(&mut v0, &mut v1);
+        }
+
+        let size = params.dest_size.unwrap_or(vec2(sw, sh));
+        let pivot = params.pivot.unwrap_or(vec2(x + size.x / 2., y + size.y / 2.));
+        let rotation = Mat2::from_angle(params.rotation);
+
+        let corners = [
+            (vec2(x, y), u0, v0),
+            (vec2(x + size.x, y), u1, v0),
+            (vec2(x + size.x, y + size.y), u1, v1),
+            (vec2(x, y + size.y), u0, v1),
+        ];
+
+        let vertices = corners.map(|(p, u, v)| {
+            let p = pivot + rotation * (p - pivot);
+            Vertex::new(p.x, p.y, u, v, color)
+        });
+
+        self.batch().push(texture, &vertices, &[0, 1, 2, 0, 2, 3]);
+    }
+}
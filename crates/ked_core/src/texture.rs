@@ -0,0 +1,269 @@
+use crate::*;
+
+/// 纹理采样的过滤方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FilterMode {
+    /// 线性插值，适合一般的贴图
+    #[default]
+    Linear,
+    /// 最近点采样，适合像素风格的贴图
+    Nearest,
+}
+
+impl From<FilterMode> for wgpu::FilterMode {
+    fn from(filter: FilterMode) -> Self {
+        match filter {
+            FilterMode::Linear => wgpu::FilterMode::Linear,
+            FilterMode::Nearest => wgpu::FilterMode::Nearest,
+        }
+    }
+}
+
+struct TextureInner {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    filter: Mutex<FilterMode>,
+    bind_group: Mutex<Arc<BindGroup>>,
+}
+
+/// GPU 上的 2D 纹理句柄，克隆开销很小（内部共享同一份资源）
+#[derive(Clone)]
+pub struct Texture2D {
+    inner: Arc<TextureInner>,
+}
+
+impl Texture2D {
+    /// 用 RGBA8 像素数据创建纹理，`bytes` 的长度必须是 `width * height * 4`
+    ///
+    /// 大小为 0、超过设备支持的最大尺寸或数据长度不对时返回错误
+    pub fn from_rgba8(
+        context: &GraphicsContext,
+        width: u32,
+        height: u32,
+        bytes: &[u8],
+    ) -> image::ImageResult<Self> {
+        Self::from_rgba8_with_filter(context, width, height, bytes, FilterMode::default())
+    }
+
+    pub fn from_rgba8_with_filter(
+        context: &GraphicsContext,
+        width: u32,
+        height: u32,
+        bytes: &[u8],
+        filter: FilterMode,
+    ) -> image::ImageResult<Self> {
+        check_texture_size(width, height, context.device.limits().max_texture_dimension_2d)?;
+
+        let expected_len = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(4));
+
+        if expected_len != Some(bytes.len()) {
+            return Err(image::ImageError::Parameter(image::error::ParameterError::from_kind(
+                image::error::ParameterErrorKind::DimensionMismatch,
+            )));
+        }
+
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = context.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("texture_2d"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        context.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytes,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = create_texture_bind_group(context, &view, filter);
+
+        Ok(Self {
+            inner: Arc::new(TextureInner {
+                texture,
+                view,
+                filter: Mutex::new(filter),
+                bind_group: Mutex::new(Arc::new(bind_group)),
+            }),
+        })
+    }
+
+    /// 从 PNG / JPEG 等编码后的图片数据创建纹理，`format` 为 `None` 时自动识别
+    pub fn from_file_with_format(
+        context: &GraphicsContext,
+        bytes: &[u8],
+        format: Option<ImageFormat>,
+    ) -> image::ImageResult<Self> {
+        let image = match format {
+            Some(format) => image::load_from_memory_with_format(bytes, format)?,
+            None => image::load_from_memory(bytes)?,
+        };
+
+        Self::from_image(context, &image.to_rgba8())
+    }
+
+    pub fn from_image(context: &GraphicsContext, image: &image::RgbaImage) -> image::ImageResult<Self> {
+        Self::from_rgba8(context, image.width(), image.height(), image.as_raw())
+    }
+
+    /// 1x1 的白色纹理，纯色图形都用它来绘制
+    pub fn white(context: &GraphicsContext) -> Self {
+        Self::from_rgba8(context, 1, 1, &[255, 255, 255, 255]).expect("1x1 texture is always valid")
+    }
+
+    /// 修改采样方式，所有共享这份纹理的句柄都会生效
+    pub fn set_filter(&self, context: &GraphicsContext, filter: FilterMode) {
+        let mut current = self.inner.filter.lock();
+        if *current == filter {
+            return;
+        }
+
+        *current = filter;
+        *self.inner.bind_group.lock() =
+            Arc::new(create_texture_bind_group(context, &self.inner.view, filter));
+    }
+
+    pub fn filter(&self) -> FilterMode {
+        *self.inner.filter.lock()
+    }
+
+    pub fn width(&self) -> f32 {
+        self.inner.texture.width() as f32
+    }
+
+    pub fn height(&self) -> f32 {
+        self.inner.texture.height() as f32
+    }
+
+    pub fn size(&self) -> Vec2 {
+        vec2(self.width(), self.height())
+    }
+
+    pub(crate) fn bind_group(&self) -> Arc<BindGroup> {
+        self.inner.bind_group.lock().clone()
+    }
+
+    /// 两个句柄是否指向同一份 GPU 纹理
+    pub(crate) fn same_texture(&self, other: &Texture2D) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+/// 宽高不能为 0，也不能超过设备支持的 `max_dimension`
+fn check_texture_size(width: u32, height: u32, max_dimension: u32) -> image::ImageResult<()> {
+    if width == 0 || height == 0 || width > max_dimension || height > max_dimension {
+        return Err(image::ImageError::Limits(image::error::LimitError::from_kind(
+            image::error::LimitErrorKind::DimensionError,
+        )));
+    }
+
+    Ok(())
+}
+
+/// 从 PNG 等图片文件的内容创建窗口图标
+pub fn load_icon(bytes: &[u8]) -> image::ImageResult<Icon> {
+    let image = image::load_from_memory(bytes)?.to_rgba8();
+    let (width, height) = image.dimensions();
+
+    Icon::from_rgba(image.into_raw(), width, height).map_err(|err| {
+        image::ImageError::Parameter(image::error::ParameterError::from_kind(
+            image::error::ParameterErrorKind::Generic(err.to_string()),
+        ))
+    })
+}
+
+fn create_texture_bind_group(
+    context: &GraphicsContext,
+    view: &wgpu::TextureView,
+    filter: FilterMode,
+) -> BindGroup {
+    let sampler = context.device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("texture_2d_sampler"),
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        address_mode_w: wgpu::AddressMode::ClampToEdge,
+        mag_filter: filter.into(),
+        min_filter: filter.into(),
+        mipmap_filter: wgpu::FilterMode::Nearest,
+        ..Default::default()
+    });
+
+    context.device.create_bind_group(&BindGroupDescriptor {
+        label: Some("texture_2d_bind_group"),
+        layout: &context.texture_layout,
+        entries: &[
+            BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(&sampler),
+            },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn texture_size_must_fit_the_device() {
+        assert!(check_texture_size(1, 1, 4096).is_ok());
+        assert!(check_texture_size(4096, 4096, 4096).is_ok());
+
+        assert!(check_texture_size(0, 16, 4096).is_err());
+        assert!(check_texture_size(16, 0, 4096).is_err());
+        assert!(check_texture_size(5000, 16, 4096).is_err());
+        assert!(check_texture_size(16, 5000, 4096).is_err());
+    }
+
+    #[test]
+    fn oversized_image_is_an_error() {
+        if !headless_adapter_available() {
+            eprintln!("skipping: no adapter available for headless rendering");
+            return;
+        }
+
+        let renderer = WgpuRenderer::new_headless(
+            Arc::new(Mutex::new(WindowConfig::default())),
+            PhysicalSize::new(8, 8),
+        )
+        .block_on();
+
+        let max = renderer.context.device.limits().max_texture_dimension_2d;
+        let mut png = Vec::new();
+        image::RgbaImage::new(max + 1, 1)
+            .write_to(&mut std::io::Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        assert!(renderer.load_texture(&png, FilterMode::Nearest).is_err());
+        assert!(Texture2D::from_rgba8(&renderer.context, 0, 0, &[]).is_err());
+        assert!(Texture2D::from_rgba8(&renderer.context, 2, 2, &[0; 4]).is_err());
+        assert!(Texture2D::from_rgba8(&renderer.context, 1, 1, &[0; 4]).is_ok());
+    }
+}
@@ -1,68 +1,447 @@
 use crate::*;
 
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+/// 统计帧率时使用的帧数
+const FRAME_TIME_WINDOW: usize = 120;
+
+/// 时间缩放的上限，过大的值会让 delta_time 溢出
+pub const MAX_TIME_SCALE: f32 = 100.0;
+
+/// 时间来源，返回从某个固定起点开始经过的时间
+pub trait Clock {
+    fn now(&mut self) -> Duration;
+}
+
+/// 系统时钟
+#[derive(Debug, Clone, Copy)]
+pub struct RealClock {
+    start: Instant,
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RealClock {
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+impl Clock for RealClock {
+    fn now(&mut self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// 手动推进的时钟，克隆出来的句柄共享同一个时间，适合在测试里精确控制 `Time`
+///
+/// ```
+/// use ked_core::*;
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let mut time = Time::with_clock(clock.clone());
+///
+/// clock.advance(Duration::from_millis(16));
+/// time.update();
+///
+/// assert!((time.get_delta_time() - 0.016).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, delta: Duration) {
+        *self.now.lock() += delta;
+    }
+
+    pub fn set(&self, now: Duration) {
+        *self.now.lock() = now;
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        *self.now.lock()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&mut self) -> Duration {
+        self.elapsed()
+    }
+}
+
+/// 最近若干帧的帧时间统计
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    pub average_fps: f32,
+    pub min_fps: f32,
+    pub max_fps: f32,
+    /// 以下单位均为秒
+    pub average_frame_time: f32,
+    pub min_frame_time: f32,
+    pub max_frame_time: f32,
+}
+
 pub struct Time {
-    start_time: Instant,
+    clock: Box<dyn Clock>,
     current_time: Duration,
+    unscaled_time: Duration,
     delta_time: Duration,
-    fps: f64,
+    unscaled_delta_time: Duration,
     frame_count: usize,
-    last_time: Instant,
+    last_time: Duration,
+
+    time_scale: f64,
+    paused: bool,
+
+    frame_times: VecDeque<Duration>,
+    frame_times_sum: Duration,
+    stats: FrameStats,
+
+    fixed_delta_time: Duration,
+    max_fixed_steps: u32,
+    accumulator: Duration,
+    alpha: f64,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Time {
     /// 创建一个新的 Time 实例
     pub fn new() -> Self {
-        let start_time = Instant::now();
+        Self::with_clock(RealClock::new())
+    }
+
+    /// 使用指定的时钟创建 Time
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        let mut clock: Box<dyn Clock> = Box::new(clock);
+        let last_time = clock.now();
+
         Self {
-            start_time,
+            clock,
             current_time: Duration::new(0, 0),
+            unscaled_time: Duration::new(0, 0),
             delta_time: Duration::new(0, 0),
-            fps: 0.0,
+            unscaled_delta_time: Duration::new(0, 0),
             frame_count: 0,
-            last_time: start_time,
+            last_time,
+
+            time_scale: 1.0,
+            paused: false,
+
+            frame_times: VecDeque::with_capacity(FRAME_TIME_WINDOW),
+            frame_times_sum: Duration::ZERO,
+            stats: FrameStats::default(),
+
+            fixed_delta_time: Duration::from_secs_f64(1.0 / 60.0),
+            max_fixed_steps: 5,
+            accumulator: Duration::ZERO,
+            alpha: 0.0,
         }
     }
 
+    /// 替换时钟，下一次 `update` 从新时钟的当前时间开始计算
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = Box::new(clock);
+        self.last_time = self.clock.now();
+    }
+
+    /// 设置 fixed_update 的步长和每帧最多追赶的步数
+    pub fn set_fixed_timestep(&mut self, step: Duration, max_steps: u32) {
+        assert!(!step.is_zero(), "fixed timestep must be greater than zero");
+
+        self.fixed_delta_time = step;
+        self.max_fixed_steps = max_steps.max(1);
+    }
+
+    /// 把本帧的 delta_time 累加进来，返回本帧需要执行的 fixed_update 次数
+    ///
+    /// 超过 `max_fixed_steps` 的部分会被丢弃，避免卡顿之后越追越慢
+    pub(crate) fn fixed_steps(&mut self) -> u32 {
+        self.accumulator += self.delta_time;
+
+        let mut steps = 0;
+        while self.accumulator >= self.fixed_delta_time && steps < self.max_fixed_steps {
+            self.accumulator -= self.fixed_delta_time;
+            steps += 1;
+        }
+
+        if self.accumulator >= self.fixed_delta_time {
+            self.accumulator = Duration::ZERO;
+        }
+
+        self.alpha = self.accumulator.as_secs_f64() / self.fixed_delta_time.as_secs_f64();
+
+        steps
+    }
+
     /// 更新时间数据并计算 delta_time 和 fps
     pub fn update(&mut self) {
-        let now = Instant::now();
-        self.current_time = now.duration_since(self.start_time);
-        
-        // 计算 delta_time
-        self.delta_time = now.duration_since(self.last_time);
-        
-        // 计算 fps
-        if self.delta_time.as_secs_f64() > 0.0 { // 防止除以零
-            self.fps = 1.0 / self.delta_time.as_secs_f64();
-        }
+        let now = self.clock.now();
+        let delta = now.saturating_sub(self.last_time);
 
         // 更新时间戳
         self.last_time = now;
+
+        self.advance(delta);
+    }
+
+    /// 不读取时钟，直接让时间前进 `delta`，用于需要确定性结果的场景
+    pub fn step(&mut self, delta: Duration) {
+        self.last_time = self.clock.now();
+
+        self.advance(delta);
+    }
+
+    fn advance(&mut self, delta: Duration) {
+        self.frame_count += 1;
+
+        self.unscaled_delta_time = delta;
+        self.unscaled_time += delta;
+
+        // 暂停时游戏时间不前进
+        self.delta_time = if self.paused {
+            Duration::ZERO
+        } else {
+            Duration::try_from_secs_f64(delta.as_secs_f64() * self.time_scale).unwrap_or(Duration::MAX)
+        };
+        self.current_time += self.delta_time;
+
+        self.record_frame_time(delta);
+    }
+
+    fn record_frame_time(&mut self, delta: Duration) {
+        if self.frame_times.len() == FRAME_TIME_WINDOW {
+            if let Some(oldest) = self.frame_times.pop_front() {
+                self.frame_times_sum -= oldest;
+            }
+        }
+
+        self.frame_times.push_back(delta);
+        self.frame_times_sum += delta;
+
+        let average = self.frame_times_sum.as_secs_f32() / self.frame_times.len() as f32;
+        let min = self.frame_times.iter().min().copied().unwrap_or_default().as_secs_f32();
+        let max = self.frame_times.iter().max().copied().unwrap_or_default().as_secs_f32();
+
+        // 防止除以零
+        let fps = |frame_time: f32| if frame_time > 0.0 { 1.0 / frame_time } else { 0.0 };
+
+        self.stats = FrameStats {
+            average_fps: fps(average),
+            min_fps: fps(max),
+            max_fps: fps(min),
+            average_frame_time: average,
+            min_frame_time: min,
+            max_frame_time: max,
+        };
+    }
+
+    /// 设置时间缩放，影响 delta_time、游戏时间和 fixed_update
+    ///
+    /// 限制在 0.0 到 `MAX_TIME_SCALE` 之间，NaN 和无穷大会被忽略
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        if !time_scale.is_finite() {
+            warn!("Ignoring non-finite time scale {time_scale}");
+            return;
+        }
+
+        self.time_scale = time_scale.clamp(0.0, MAX_TIME_SCALE) as f64;
+    }
+
+    pub fn get_time_scale(&self) -> f32 {
+        self.time_scale as f32
+    }
+
+    /// 暂停游戏时间，暂停期间 delta_time 为 0，fixed_update 不会执行
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
     }
 
-    /// 获取当前时间
+    /// 获取当前游戏时间（受缩放和暂停影响）
     pub fn get_time(&self) -> f32 {
         self.current_time.as_secs_f32()
     }
 
-    /// 获取 delta_time
+    /// 获取启动以来的真实时间
+    pub fn get_unscaled_time(&self) -> f32 {
+        self.unscaled_time.as_secs_f32()
+    }
+
+    /// 获取 delta_time（受缩放和暂停影响）
     pub fn get_delta_time(&self) -> f32 {
         self.delta_time.as_secs_f32()
     }
 
-    /// 获取每秒帧数
+    /// 获取真实的帧间隔
+    pub fn get_unscaled_delta_time(&self) -> f32 {
+        self.unscaled_delta_time.as_secs_f32()
+    }
+
+    /// 获取 fixed_update 的步长
+    pub fn get_fixed_delta_time(&self) -> f32 {
+        self.fixed_delta_time.as_secs_f32()
+    }
+
+    /// 距离下一次 fixed_update 的进度（0.0 到 1.0），用于在两次物理状态之间插值渲染
+    pub fn get_alpha(&self) -> f32 {
+        self.alpha as f32
+    }
+
+    /// 获取已经更新的帧数
+    pub fn get_frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// 获取每秒帧数（最近若干帧的平均值）
     pub fn get_fps(&self) -> u32 {
-        self.fps.round() as u32
+        self.stats.average_fps.round() as u32
+    }
+
+    /// 获取最近若干帧的帧率和帧时间统计
+    pub fn get_frame_stats(&self) -> FrameStats {
+        self.stats
     }
 
     pub fn println_time_data(&self) {
         println!(
-            "time: {:.6}, deltaTime: {:.6}, fps: {}",
+            "time: {:.6}, deltaTime: {:.6}, fps: {}, frame: {}",
             self.current_time.as_secs_f32(),
             self.delta_time.as_secs_f32(),
-            self.fps.round() as u32
+            self.get_fps(),
+            self.frame_count
         );
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(time: &mut Time, millis: u64) {
+        time.step(Duration::from_millis(millis));
+    }
+
+    #[test]
+    fn manual_clock_drives_delta_time() {
+        let clock = ManualClock::new();
+        let mut time = Time::with_clock(clock.clone());
+
+        clock.advance(Duration::from_millis(20));
+        time.update();
+        clock.advance(Duration::from_millis(30));
+        time.update();
+
+        assert_eq!(time.get_frame_count(), 2);
+        assert!((time.get_delta_time() - 0.03).abs() < 1e-6);
+        assert!((time.get_time() - 0.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn scale_and_pause_only_affect_game_time() {
+        let mut time = Time::new();
+
+        time.set_time_scale(0.5);
+        frame(&mut time, 100);
+
+        assert!((time.get_delta_time() - 0.05).abs() < 1e-6);
+        assert!((time.get_unscaled_delta_time() - 0.1).abs() < 1e-6);
+
+        time.pause();
+        frame(&mut time, 100);
+
+        assert_eq!(time.get_delta_time(), 0.0);
+        assert_eq!(time.fixed_steps(), 0);
+        assert!((time.get_time() - 0.05).abs() < 1e-6);
+        assert!((time.get_unscaled_time() - 0.2).abs() < 1e-6);
+
+        time.resume();
+        frame(&mut time, 100);
+
+        assert!((time.get_time() - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn time_scale_is_clamped() {
+        let mut time = Time::new();
+
+        time.set_time_scale(2.0);
+        time.set_time_scale(f32::INFINITY);
+        time.set_time_scale(f32::NAN);
+        assert_eq!(time.get_time_scale(), 2.0);
+
+        time.set_time_scale(f32::MAX);
+        assert_eq!(time.get_time_scale(), MAX_TIME_SCALE);
+
+        time.set_time_scale(-1.0);
+        assert_eq!(time.get_time_scale(), 0.0);
+
+        time.set_time_scale(MAX_TIME_SCALE);
+        frame(&mut time, 100);
+        assert!((time.get_delta_time() - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fixed_steps_accumulate() {
+        let mut time = Time::new();
+        time.set_fixed_timestep(Duration::from_millis(10), 3);
+
+        frame(&mut time, 25);
+        assert_eq!(time.fixed_steps(), 2);
+        assert!((time.get_alpha() - 0.5).abs() < 1e-4);
+
+        frame(&mut time, 5);
+        assert_eq!(time.fixed_steps(), 1);
+
+        // 超过上限的部分被丢弃
+        frame(&mut time, 100);
+        assert_eq!(time.fixed_steps(), 3);
+        assert_eq!(time.get_alpha(), 0.0);
+    }
+
+    #[test]
+    fn frame_stats_use_rolling_window() {
+        let mut time = Time::new();
+
+        frame(&mut time, 10);
+        frame(&mut time, 20);
+        frame(&mut time, 30);
+
+        let stats = time.get_frame_stats();
+        assert!((stats.average_frame_time - 0.02).abs() < 1e-6);
+        assert!((stats.min_frame_time - 0.01).abs() < 1e-6);
+        assert!((stats.max_frame_time - 0.03).abs() < 1e-6);
+        assert_eq!(time.get_fps(), 50);
+
+        for _ in 0..FRAME_TIME_WINDOW {
+            frame(&mut time, 10);
+        }
+
+        let stats = time.get_frame_stats();
+        assert!((stats.max_frame_time - 0.01).abs() < 1e-6);
+        assert_eq!(time.get_fps(), 100);
+    }
+}
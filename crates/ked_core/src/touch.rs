@@ -0,0 +1,334 @@
+use crate::*;
+
+use std::collections::HashMap;
+
+/// 一根手指的状态，坐标为物理像素
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: Vec2,
+    pub start_position: Vec2,
+    /// 0.0 到 1.0，设备不支持压力时为 1.0
+    pub pressure: f32,
+    pub start_time: f32,
+    /// 本帧按下的触点，即使同一帧里已经移动或抬起也为真
+    pub started_this_frame: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap {
+        position: Vec2,
+    },
+    Swipe {
+        start: Vec2,
+        end: Vec2,
+        /// 单位向量
+        direction: Vec2,
+        /// 像素每秒
+        speed: f32,
+    },
+    Pinch {
+        center: Vec2,
+        /// 当前两指距离与开始捏合时距离的比值
+        scale: f32,
+    },
+}
+
+/// 手势识别的阈值
+#[derive(Debug, Clone, Copy)]
+pub struct GestureConfig {
+    /// 点击允许的最大移动距离（像素）
+    pub tap_max_distance: f32,
+    /// 点击允许的最长按下时间（秒）
+    pub tap_max_duration: f32,
+    /// 滑动需要的最小距离（像素）
+    pub swipe_min_distance: f32,
+    /// 滑动允许的最长时间（秒）
+    pub swipe_max_duration: f32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            tap_max_distance: 20.0,
+            tap_max_duration: 0.3,
+            swipe_min_distance: 50.0,
+            swipe_max_duration: 0.5,
+        }
+    }
+}
+
+/// 多点触控状态和简单的手势识别
+#[derive(Debug, Default)]
+pub struct Touches {
+    pub config: GestureConfig,
+
+    active: HashMap<u64, Touch>,
+    /// 本帧结束（或取消）的触点
+    ended: Vec<Touch>,
+    gestures: Vec<Gesture>,
+
+    /// 捏合开始时两指的距离
+    pinch_start_distance: Option<f32>,
+    /// 本次触摸过程中出现过多指，此时不再识别点击和滑动
+    multi_touch: bool,
+}
+
+impl Touches {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 处理一个触摸事件，`time` 为事件发生时的时间（秒）
+    pub fn touch(&mut self, id: u64, phase: TouchPhase, position: Vec2, pressure: f32, time: f32) {
+        match phase {
+            TouchPhase::Started => {
+                self.active.insert(id, Touch {
+                    id,
+                    phase,
+                    position,
+                    start_position: position,
+                    pressure,
+                    start_time: time,
+                    started_this_frame: true,
+                });
+
+                if self.active.len() > 1 {
+                    self.multi_touch = true;
+                }
+
+                self.pinch_start_distance = self.two_finger_distance();
+            }
+            TouchPhase::Moved => {
+                if let Some(touch) = self.active.get_mut(&id) {
+                    touch.phase = phase;
+                    touch.position = position;
+                    touch.pressure = pressure;
+                }
+
+                self.update_pinch();
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                let Some(mut touch) = self.active.remove(&id) else {
+                    return;
+                };
+
+                touch.phase = phase;
+                touch.position = position;
+
+                if phase == TouchPhase::Ended && !self.multi_touch {
+                    self.recognize(&touch, time);
+                }
+
+                self.ended.push(touch);
+
+                self.pinch_start_distance = self.two_finger_distance();
+                if self.active.is_empty() {
+                    self.multi_touch = false;
+                }
+            }
+        }
+    }
+
+    /// 单指抬起时识别点击或滑动
+    fn recognize(&mut self, touch: &Touch, time: f32) {
+        let delta = touch.position - touch.start_position;
+        let distance = delta.length();
+        let duration = time - touch.start_time;
+
+        if distance <= self.config.tap_max_distance && duration <= self.config.tap_max_duration {
+            self.gestures.push(Gesture::Tap { position: touch.position });
+        } else if distance >= self.config.swipe_min_distance
+            && duration <= self.config.swipe_max_duration
+        {
+            self.gestures.push(Gesture::Swipe {
+                start: touch.start_position,
+                end: touch.position,
+                direction: delta / distance,
+                speed: distance / duration.max(f32::EPSILON),
+            });
+        }
+    }
+
+    fn two_finger_distance(&self) -> Option<f32> {
+        let mut touches = self.active.values();
+
+        match (touches.next(), touches.next(), touches.next()) {
+            (Some(a), Some(b), None) => Some(a.position.distance(b.position)),
+            _ => None,
+        }
+    }
+
+    fn update_pinch(&mut self) {
+        let (Some(start), Some(current)) = (self.pinch_start_distance, self.two_finger_distance())
+        else {
+            return;
+        };
+
+        if start <= f32::EPSILON {
+            return;
+        }
+
+        let center = self.active.values().map(|t| t.position).sum::<Vec2>() / 2.0;
+        let pinch = Gesture::Pinch { center, scale: current / start };
+
+        // 每帧只保留最新的捏合状态
+        match self.gestures.iter_mut().find(|g| matches!(g, Gesture::Pinch { .. })) {
+            Some(gesture) => *gesture = pinch,
+            None => self.gestures.push(pinch),
+        }
+    }
+
+    /// 每帧更新结束后清除只持续一帧的状态
+    pub(crate) fn end_frame(&mut self) {
+        self.ended.clear();
+        self.gestures.clear();
+
+        for touch in self.active.values_mut() {
+            if touch.phase == TouchPhase::Started {
+                touch.phase = TouchPhase::Moved;
+            }
+
+            touch.started_this_frame = false;
+        }
+    }
+
+    /// 当前按在屏幕上的触点，以及本帧刚抬起的触点
+    pub fn touches(&self) -> impl Iterator<Item = &Touch> {
+        self.active.values().chain(self.ended.iter())
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Touch> {
+        self.active.get(&id)
+    }
+
+    pub fn count(&self) -> usize {
+        self.active.len()
+    }
+
+    /// 本帧识别到的手势
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
+
+    pub fn clear(&mut self) {
+        self.active.clear();
+        self.ended.clear();
+        self.gestures.clear();
+        self.pinch_start_distance = None;
+        self.multi_touch = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_fingers_by_id() {
+        let mut touches = Touches::new();
+
+        touches.touch(1, TouchPhase::Started, vec2(10.0, 10.0), 0.5, 0.0);
+        touches.touch(2, TouchPhase::Started, vec2(100.0, 10.0), 1.0, 0.0);
+        touches.touch(1, TouchPhase::Moved, vec2(15.0, 12.0), 0.7, 0.1);
+
+        assert_eq!(touches.count(), 2);
+
+        let first = touches.get(1).unwrap();
+        assert_eq!(first.phase, TouchPhase::Moved);
+        assert_eq!(first.position, vec2(15.0, 12.0));
+        assert_eq!(first.start_position, vec2(10.0, 10.0));
+        assert_eq!(first.pressure, 0.7);
+
+        touches.touch(2, TouchPhase::Ended, vec2(100.0, 10.0), 1.0, 0.2);
+
+        assert_eq!(touches.count(), 1);
+        assert!(touches.touches().any(|t| t.id == 2 && t.phase == TouchPhase::Ended));
+
+        touches.end_frame();
+
+        assert!(touches.touches().all(|t| t.id == 1 && !t.started_this_frame));
+    }
+
+    #[test]
+    fn short_touch_is_a_tap() {
+        let mut touches = Touches::new();
+
+        touches.touch(0, TouchPhase::Started, vec2(50.0, 50.0), 1.0, 1.0);
+        touches.touch(0, TouchPhase::Moved, vec2(52.0, 51.0), 1.0, 1.05);
+        touches.touch(0, TouchPhase::Ended, vec2(52.0, 51.0), 1.0, 1.1);
+
+        assert_eq!(touches.gestures(), &[Gesture::Tap { position: vec2(52.0, 51.0) }]);
+        assert!(touches.touches().all(|t| t.started_this_frame));
+
+        touches.end_frame();
+        assert!(touches.gestures().is_empty());
+    }
+
+    #[test]
+    fn long_press_is_not_a_tap() {
+        let mut touches = Touches::new();
+
+        touches.touch(0, TouchPhase::Started, vec2(50.0, 50.0), 1.0, 0.0);
+        touches.touch(0, TouchPhase::Ended, vec2(50.0, 50.0), 1.0, 1.0);
+
+        assert!(touches.gestures().is_empty());
+    }
+
+    #[test]
+    fn fast_movement_is_a_swipe() {
+        let mut touches = Touches::new();
+
+        touches.touch(0, TouchPhase::Started, vec2(0.0, 100.0), 1.0, 0.0);
+        touches.touch(0, TouchPhase::Moved, vec2(100.0, 100.0), 1.0, 0.1);
+        touches.touch(0, TouchPhase::Ended, vec2(200.0, 100.0), 1.0, 0.2);
+
+        match touches.gestures() {
+            [Gesture::Swipe { start, end, direction, speed }] => {
+                assert_eq!(*start, vec2(0.0, 100.0));
+                assert_eq!(*end, vec2(200.0, 100.0));
+                assert_eq!(*direction, vec2(1.0, 0.0));
+                assert!((speed - 1000.0).abs() < 0.01);
+            }
+            other => panic!("expected a swipe, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cancelled_touch_has_no_gesture() {
+        let mut touches = Touches::new();
+
+        touches.touch(0, TouchPhase::Started, vec2(50.0, 50.0), 1.0, 0.0);
+        touches.touch(0, TouchPhase::Cancelled, vec2(50.0, 50.0), 1.0, 0.05);
+
+        assert!(touches.gestures().is_empty());
+        assert_eq!(touches.count(), 0);
+    }
+
+    #[test]
+    fn two_fingers_pinch() {
+        let mut touches = Touches::new();
+
+        touches.touch(0, TouchPhase::Started, vec2(100.0, 100.0), 1.0, 0.0);
+        touches.touch(1, TouchPhase::Started, vec2(200.0, 100.0), 1.0, 0.0);
+        touches.touch(0, TouchPhase::Moved, vec2(50.0, 100.0), 1.0, 0.1);
+        touches.touch(1, TouchPhase::Moved, vec2(250.0, 100.0), 1.0, 0.1);
+
+        assert_eq!(
+            touches.gestures(),
+            &[Gesture::Pinch { center: vec2(150.0, 100.0), scale: 2.0 }]
+        );
+
+        // 捏合结束后抬起手指不会被识别为点击
+        touches.touch(0, TouchPhase::Ended, vec2(50.0, 100.0), 1.0, 0.15);
+        touches.touch(1, TouchPhase::Ended, vec2(250.0, 100.0), 1.0, 0.15);
+        touches.end_frame();
+
+        touches.touch(2, TouchPhase::Started, vec2(10.0, 10.0), 1.0, 1.0);
+        touches.touch(2, TouchPhase::Ended, vec2(10.0, 10.0), 1.0, 1.1);
+
+        assert_eq!(touches.gestures(), &[Gesture::Tap { position: vec2(10.0, 10.0) }]);
+    }
+}
@@ -49,7 +49,7 @@ pub fn ked_main(attr: TokenStream, item: TokenStream) -> TokenStream {
         if let Pat::Ident(pat_ident) = &*pat_type.pat {
             if pat_ident.ident != "event_loop" {
                 return syn::Error::new_spanned(
-                    &pat_ident,
+                    pat_ident,
                     "Parameter must be named 'event_loop'"
                 ).to_compile_error().into();
             }
@@ -104,6 +104,10 @@ pub fn ked_main(attr: TokenStream, item: TokenStream) -> TokenStream {
                 Config::default().with_max_level(#log_level),
             );
 
+            if let Some(dir) = app.internal_data_path() {
+                WindowConfig::set_settings_dir(dir);
+            }
+
             let event_loop = EventLoopBuilder::new()
                 .with_android_app(app)
                 .build()